@@ -1,100 +1,786 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    fmt, io,
+    ops::Index,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 // --- Type Definitions --- //
 
 #[derive(Debug, Deserialize)]
+#[serde(try_from = "Value")]
 pub struct RustDoc {
     root: String,
-    crate_version: String,
+    crate_version: Option<String>,
     includes_private: bool,
     index: HashMap<String, RustDocItem>,
+    paths: HashMap<String, ItemSummary>,
+    /// From the id of each associated item of a local impl or trait to the
+    /// name of its type or trait, e.g. `Wrapper`, and the type or trait's id
+    /// if it has one. Built on first use.
+    assoc_item_owners: OnceLock<HashMap<String, (String, Option<String>)>>,
+    /// From the id of each named field of a local struct to the struct's id.
+    /// Built on first use.
+    field_owners: OnceLock<HashMap<String, String>>,
+}
+
+/// The top-level fields of rustdoc's JSON, as read before [`RustDoc`] fills
+/// in anything rustdoc left out.
+#[derive(Deserialize)]
+struct RustDocJson {
+    root: String,
+    crate_version: Option<String>,
+    includes_private: bool,
+    index: HashMap<String, RustDocItem>,
+    paths: HashMap<String, ItemSummary>,
+}
+
+/// An entry in rustdoc's `paths` map: the fully-qualified path of an item.
+#[derive(Debug, Deserialize, Serialize)]
+struct ItemSummary {
+    crate_id: u32,
+    path: Vec<String>,
+    kind: RustDocItemKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RustDocItemKind {
+    Module,
+    ExternCrate,
+    Import,
+    Struct,
+    StructField,
+    Union,
+    Enum,
+    Variant,
+    Function,
+    TypeAlias,
+    OpaqueTy,
+    Constant,
+    Trait,
+    TraitAlias,
+    Impl,
+    Static,
+    ForeignType,
+    Macro,
+    ProcAttribute,
+    ProcDerive,
+    AssocConst,
+    AssocType,
+    Primitive,
+    Keyword,
+}
+
+/// The attributes which are printed when requested, e.g. with
+/// [`PrintOptions::variant_attrs`]. Others, like `#[allow(..)]` or
+/// `#[inline]`, don't affect how an item is used.
+pub const ATTR_ALLOWLIST: &[&str] =
+    &["default", "non_exhaustive", "repr", "serde", "must_use"];
+
+const DEFAULT_MAX_TYPE_DEPTH: usize = 32;
+const DEFAULT_TRUNCATION_MARKER: &str = "...";
+
+thread_local! {
+    /// How deeply nested the type being displayed is, and the depth beyond
+    /// which types are elided. Types are displayed without access to
+    /// [`PrintOptions`], so [`PrintOptions::max_type_depth`] is set here by
+    /// [`MaxTypeDepth`] for the duration of each item.
+    static TYPE_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static MAX_TYPE_DEPTH: Cell<usize> =
+        const { Cell::new(DEFAULT_MAX_TYPE_DEPTH) };
+    /// What elided types are displayed as: the
+    /// [`PrintOptions::truncation_marker`], set by [`TruncationMarker`].
+    static TRUNCATION_MARKER: RefCell<String> =
+        RefCell::new(DEFAULT_TRUNCATION_MARKER.to_owned());
+}
+
+/// Options controlling how [`RustDoc`] is printed.
+#[derive(Clone, Debug)]
+pub struct PrintOptions {
+    /// Whether to expand each trait impl to show the associated consts, types,
+    /// and methods it provides, rather than just the `impl Trait for Type`.
+    pub expand_impl_items: bool,
+    /// Whether to print the target of each `pub use` re-export under the
+    /// re-exported name, annotated with the target's original path.
+    pub inline_reexports: bool,
+    /// The output format. See [`OutputFormat`].
+    pub format: OutputFormat,
+    /// The order to print items in. See [`SortMode`].
+    pub sort: SortMode,
+    /// The language tag on code fences, e.g. `rust` for ```` ```rust ````.
+    /// `None` emits bare ```` ``` ```` fences. Defaults to `rust`.
+    pub code_fence_lang: Option<String>,
+    /// Whether to print a warning to stderr for each item which isn't
+    /// printed because it has no name or docs. See [`RustDoc::dropped_items`].
+    pub warn_dropped: bool,
+    /// The marker for elided content, e.g. the items of a collapsed impl in
+    /// `impl Clone for Foo { ... }`. Defaults to the ASCII `...`; set it to
+    /// `…` for a more compact, non-ASCII marker.
+    pub truncation_marker: String,
+    /// If set, the definitions of local enums with at most this many variants
+    /// are repeated under each function whose signature mentions them, so
+    /// readers needn't look them up. Off by default.
+    pub inline_enums_max_variants: Option<usize>,
+    /// Whether to list the blanket impls which apply to this crate's types,
+    /// e.g. `impl<T: Display> ToString for T`, in a section at the end.
+    /// These are never listed under each type, as most apply to every type.
+    pub blanket_impls: bool,
+    /// Whether to list the auto traits each type implements, e.g. `Send`, on
+    /// one line under it. Only the notable auto traits it doesn't implement
+    /// are listed, e.g. `!Send`.
+    pub auto_trait_impls: bool,
+    /// Whether to print the attributes of enum variants which are part of
+    /// the API, like `#[default]` or `#[serde(rename = "..")]`. See
+    /// [`ATTR_ALLOWLIST`].
+    pub variant_attrs: bool,
+    /// If set, only the items with these ids are printed, e.g. those from
+    /// [`RustDoc::changed_since`].
+    pub item_ids: Option<HashSet<String>>,
+    /// Whether to print each item's docs exactly as written, without
+    /// rewriting intra-doc links or any other transformation.
+    pub raw_docs: bool,
+    /// If set, only items of these kinds are printed, e.g. just functions
+    /// and traits. Their impls are still printed under the types they're
+    /// for.
+    pub include_kinds: Option<HashSet<RustDocItemKind>>,
+    /// Whether to prefix each item's heading with its module path, e.g.
+    /// `` `net::tcp::TcpStream`: ``, so that items from different modules
+    /// can be told apart. Items without a path, like methods, keep their
+    /// bare names.
+    pub qualified_names: bool,
+    /// Whether to prefix the headings of methods and other associated items
+    /// with their type or trait, e.g. `` `Wrapper::new`: ``, so that those
+    /// of different types can be told apart in the list of items.
+    pub qualified_assoc_items: bool,
+    /// Whether to omit deprecated items, leaving only the recommended API.
+    /// By default they're printed with a deprecation notice.
+    pub hide_deprecated: bool,
+    /// Whether to omit items which aren't public, including the private
+    /// fields of structs, which are marked as `/* private fields */`. Items
+    /// with default visibility, like enum variants and the items of traits
+    /// and trait impls, are as public as their parent, so are kept. By
+    /// default every item with docs is printed.
+    pub public_only: bool,
+    /// Whether to note under each `#[non_exhaustive]` struct with public
+    /// fields that it can't be built with a struct literal outside its crate,
+    /// so readers look for a constructor instead.
+    pub non_exhaustive_notes: bool,
+    /// A custom transform applied to each item's docs before the built-in
+    /// ones like intra-doc link rewriting, e.g. to strip internal notes.
+    /// Skipped with [`PrintOptions::raw_docs`].
+    pub doc_transform: Option<DocTransform>,
+    /// Whether to repeat the `# Returns` section of the docs of each fn which
+    /// returns a tuple as comments below its signature, so the meaning of
+    /// each element is next to its type.
+    pub tuple_return_notes: bool,
+    /// Whether to note below the signature of each fn which returns an
+    /// `Option` or `Result` when it returns `None` or `Err`, summarizing the
+    /// `# Returns` or `# Errors` section of its docs if it has one.
+    pub fallibility_notes: bool,
+    /// Whether to note under each trait which doesn't look object safe that
+    /// it can't be used as `dyn Trait`, so readers reach for generics. Trait
+    /// methods with `where Self: Sized`, which can't be called on `dyn
+    /// Trait`, are noted too.
+    pub object_safety_notes: bool,
+    /// Whether to note under each trait which looks sealed that it can't be
+    /// implemented outside this crate, so readers don't try to. See
+    /// [`RustDoc::is_publicly_reachable`].
+    pub sealed_notes: bool,
+    /// How many levels of nested types to print before eliding the rest
+    /// with the [`truncation_marker`](Self::truncation_marker), e.g. in
+    /// `Vec<Vec<...>>`. This guards against stack overflows on pathological
+    /// JSON. Defaults to 32.
+    pub max_type_depth: usize,
+    /// The name of the module whose re-exports are listed in a "Prelude"
+    /// section before the items, as that's what users `use` first. Defaults
+    /// to `prelude`; `None` omits the section.
+    pub prelude_module: Option<String>,
+    /// Whether [`RustDocItem::try_to_markdown`] and
+    /// [`RustDoc::write_markdown`] fail on items they can't fully render,
+    /// rather than skipping them. See [`RenderError`].
+    pub strict: bool,
+    /// Whether to name unnamed fn params by their position, e.g. `arg1` for
+    /// the second param of `fn visit(&mut self, _: &str, _: usize)`, so each
+    /// param has a stable name. They're otherwise written as `_`.
+    pub positional_param_names: bool,
+    /// How many levels to demote the headings in doc comments by, so they
+    /// rank below the document's own headings, e.g. 3 turns a doc's
+    /// `# Examples` into `#### Examples`. Headings are demoted to level 6 at
+    /// most. Only applies to markdown. Defaults to 3, as items are printed
+    /// under level 2 sections.
+    pub doc_heading_offset: usize,
+    /// Whether to start the document with a YAML front matter block giving
+    /// its `title`, `crate_version` and `generated` time, for static site
+    /// generators like Jekyll or Hugo.
+    pub front_matter: bool,
+    /// Whether to list, after a trait's own items, the methods it inherits
+    /// from this crate's supertraits, including theirs in turn, so its whole
+    /// callable surface is in one place. Off by default, as they're also
+    /// printed under the supertraits.
+    pub inherited_methods: bool,
+    /// The longest const or static value expression to print in full.
+    /// Longer ones, like a big lookup table's, are elided with the
+    /// [`truncation_marker`](Self::truncation_marker), keeping any enclosing
+    /// brackets, e.g. `= [...]`. Their types are always printed in full.
+    /// Defaults to 100; `None` never elides.
+    pub max_const_value_chars: Option<usize>,
+    /// The number of predicates at which a where clause is printed with each
+    /// on its own line, as rustfmt does, rather than inline. Only applies to
+    /// the definitions of fns, structs, enums and traits, not to single-line
+    /// signatures or items nested in traits and impls. Defaults to 2, so
+    /// single predicates stay inline; `None` keeps all where clauses inline.
+    pub multiline_where_predicates: Option<usize>,
+    /// Whether to print the fns in traits and impls in fully qualified call
+    /// syntax, e.g. `fn Tally::push(self: &mut Tally<u32>, x: u32)` rather
+    /// than `fn push(&mut self, x: u32)`, spelling out the receiver's type.
+    /// Fns in trait impls are qualified as `<Type as Trait>::`.
+    pub ufcs_methods: bool,
+}
+
+/// A function from an item's docs to the docs to print. See
+/// [`PrintOptions::doc_transform`].
+#[derive(Clone)]
+pub struct DocTransform(pub Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl DocTransform {
+    pub fn new(
+        transform: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(transform))
+    }
+}
+
+impl fmt::Debug for DocTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DocTransform(..)")
+    }
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            expand_impl_items: false,
+            inline_reexports: false,
+            format: OutputFormat::default(),
+            sort: SortMode::default(),
+            code_fence_lang: Some("rust".to_owned()),
+            warn_dropped: false,
+            truncation_marker: DEFAULT_TRUNCATION_MARKER.to_owned(),
+            inline_enums_max_variants: None,
+            blanket_impls: false,
+            auto_trait_impls: false,
+            variant_attrs: false,
+            item_ids: None,
+            raw_docs: false,
+            include_kinds: None,
+            qualified_names: false,
+            qualified_assoc_items: false,
+            hide_deprecated: false,
+            public_only: false,
+            non_exhaustive_notes: false,
+            doc_transform: None,
+            tuple_return_notes: false,
+            fallibility_notes: false,
+            object_safety_notes: false,
+            sealed_notes: false,
+            max_type_depth: DEFAULT_MAX_TYPE_DEPTH,
+            prelude_module: Some("prelude".to_owned()),
+            strict: false,
+            positional_param_names: false,
+            doc_heading_offset: 3,
+            front_matter: false,
+            inherited_methods: false,
+            max_const_value_chars: Some(100),
+            multiline_where_predicates: Some(2),
+            ufcs_methods: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Markdown,
+    /// The module tree as JSON. See [`RustDoc::to_tree_json`].
+    Json,
+    /// Text without markup, e.g. for a terminal. Definitions are indented
+    /// rather than fenced, and links are just their text.
+    Plain,
+    /// AsciiDoc, with `[source,rust]` listings and `<<anchor,text>>`
+    /// cross references. Doc comments are otherwise printed as written.
+    AsciiDoc,
+    /// A standalone HTML page with a sidebar listing the items. Doc comments
+    /// are printed as preformatted text rather than converted from markdown.
+    Html,
+    /// TypeScript declarations for the types serde would serialize this
+    /// crate's items as. See [`RustDoc::to_typescript`].
+    Ts,
+}
+
+impl OutputFormat {
+    /// The names of the formats, as parsed by [`OutputFormat::from_str`].
+    pub const NAMES: &'static [&'static str] =
+        &["markdown", "json", "plain", "asciidoc", "html", "ts"];
+
+    /// The renderer for the markup around items. JSON and TypeScript aren't
+    /// rendered as markup, so they get markdown for individually printed
+    /// items.
+    fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            Self::Markdown | Self::Json | Self::Ts => &Markdown,
+            Self::Plain => &Plain,
+            Self::AsciiDoc => &AsciiDoc,
+            Self::Html => &Html,
+        }
+    }
+}
+
+/// Parses a format's name, e.g. `asciidoc`. See [`OutputFormat::NAMES`].
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "plain" => Ok(Self::Plain),
+            "asciidoc" => Ok(Self::AsciiDoc),
+            "html" => Ok(Self::Html),
+            "ts" => Ok(Self::Ts),
+            _ => {
+                let names = Self::NAMES.join(", ");
+                Err(format!("Unknown format `{name}`. Valid formats: {names}"))
+            }
+        }
+    }
+}
+
+/// The order [`RustDoc::write_markdown`] prints items in. Either way, items
+/// declared in `extern` blocks are grouped at the end.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SortMode {
+    /// The order they're declared in the source.
+    #[default]
+    Source,
+    /// By module path, then by kind, with types before traits, consts and
+    /// fns, then by name, like a reference manual. Unlike the source order,
+    /// this doesn't change when items are moved between files.
+    Structured,
+}
+
+impl SortMode {
+    /// The rank of `kind` in the [`SortMode::Structured`] order.
+    fn kind_rank(kind: Option<RustDocItemKind>) -> usize {
+        use RustDocItemKind::*;
+        let order: &[&[RustDocItemKind]] = &[
+            &[Module],
+            &[Struct, Enum, Union, TypeAlias, ForeignType, Primitive],
+            &[Trait, TraitAlias],
+            &[Constant, Static],
+            &[Function],
+            &[Macro, ProcAttribute, ProcDerive],
+        ];
+        kind.and_then(|kind| {
+            order.iter().position(|kinds| kinds.contains(&kind))
+        })
+        .unwrap_or(order.len())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RustDocItem {
+    id: String,
     docs: Option<String>,
+    /// The targets of intra-doc links in `docs`, from the link destination
+    /// as written (e.g. `` `Vec::push` ``) to the target's id.
+    #[serde(default)]
+    links: HashMap<String, String>,
+    /// e.g. `#[non_exhaustive]`. Doc comments aren't included.
+    #[serde(default)]
+    attrs: Vec<String>,
+    /// Where the item is defined. Only local items have spans.
+    span: Option<Span>,
     visibility: Option<String>,
     name: Option<String>,
+    deprecation: Option<Deprecation>,
     inner: Option<ItemInner>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct Span {
+    /// Relative to the directory `rustdoc` was invoked in.
+    filename: String,
+    /// The 1-indexed line and 0-indexed column of the first character.
+    begin: (usize, usize),
+    /// The 1-indexed line and 0-indexed column of the last character.
+    end: (usize, usize),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Deprecation {
+    since: Option<String>,
+    note: Option<String>,
+}
+
+/// A per-item text chunk for retrieval-augmented generation, produced by
+/// [`RustDoc::to_embedding_docs`]. Serialize each doc with
+/// [`serde_json::to_string`] to get JSON lines.
+#[derive(Clone, Debug, Serialize)]
+pub struct EmbeddingDoc {
+    /// The fully-qualified path, which is stable across builds.
+    pub id: String,
+    /// The item's signature and doc summary, separated by a blank line.
+    pub text: String,
+    pub metadata: EmbeddingMetadata,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EmbeddingMetadata {
+    pub kind: RustDocItemKind,
+    /// The path of the containing module, e.g. `hex` for `hex::encode`.
+    pub module: String,
+    pub deprecated: bool,
+}
+
+/// A module and its descendants, produced by [`RustDoc::module_tree`].
+#[derive(Clone, Debug, Serialize)]
+pub struct ModuleTree {
+    /// The module's name, e.g. `inner`, or the crate's name for the root.
+    pub name: String,
+    pub docs: Option<String>,
+    /// The child modules, in declaration order.
+    pub modules: Vec<ModuleTree>,
+    /// The ids of the module's other items, in declaration order. Look these
+    /// up with [`RustDoc::item`].
+    pub item_ids: Vec<String>,
+}
+
+/// The items directly within a module, produced by [`RustDoc::stats`].
+/// Child modules count as items of their parent, but their own items don't.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ModuleStats {
+    /// How many items of each kind the module contains.
+    pub kinds: HashMap<RustDocItemKind, usize>,
+    /// How many items of each kind the module contains without docs.
+    pub undocumented: HashMap<RustDocItemKind, usize>,
+}
+
+/// A flat, data-oriented listing of a crate's API, produced by
+/// [`RustDoc::summary`]. Items are sorted by path.
+#[derive(Clone, Debug, Serialize)]
+pub struct ApiSummary {
+    pub items: Vec<ApiItem>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ApiItem {
+    /// The fully-qualified path, e.g. `hex::encode`.
+    pub path: String,
+    pub kind: RustDocItemKind,
+    /// The single-line signature, if one is available for this kind of item.
+    pub signature: Option<String>,
+    /// The first paragraph of the item's docs.
+    pub docs_summary: Option<String>,
+    pub deprecated: bool,
+}
+
+/// A public item whose signature differs between two versions of a crate,
+/// e.g. a fn whose param types changed. See [`RustDoc::changed_signatures`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ChangedSignature {
+    /// The fully-qualified path, e.g. `hex::encode`.
+    pub path: String,
+    pub kind: RustDocItemKind,
+    /// The [`ApiItem::signature`] in the earlier version.
+    pub before: String,
+    /// The [`ApiItem::signature`] in this version.
+    pub after: String,
+}
+
+/// Why [`RustDoc::item_markdown`] couldn't render an item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LookupError {
+    /// No item has the given path or name.
+    NotFound(String),
+    /// Several items match; `candidates` describes each of them.
+    Ambiguous {
+        path: String,
+        candidates: Vec<String>,
+    },
+    /// The item exists but has nothing to render, e.g. it has no docs.
+    NotRendered(String),
+}
+
+/// Why an item couldn't be fully rendered with [`PrintOptions::strict`].
+///
+/// Strict rendering succeeds if every item, and every associated item of
+/// each trait and impl, is of a supported kind: modules, imports, structs
+/// and their fields, enums and their variants, fns, consts, statics, type
+/// aliases, traits, trait aliases, impls, associated consts and types, and
+/// primitives. Other kinds, like macros, are listed by
+/// [`RustDoc::unsupported_kinds`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenderError {
+    /// The item with this id is of a kind which isn't supported yet, named as
+    /// in rustdoc's JSON, e.g. `macro`.
+    UnsupportedKind { id: String, kind: String },
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ItemInner {
     function: Option<FunctionDetails>,
     #[serde(rename = "enum")]
     enum_: Option<EnumDetails>,
+    #[serde(rename = "impl")]
+    impl_: Option<ImplDetails>,
+    assoc_const: Option<AssocConstDetails>,
+    assoc_type: Option<AssocTypeDetails>,
+    constant: Option<ConstantDetails>,
+    import: Option<ImportDetails>,
+    module: Option<ModuleDetails>,
+    #[serde(rename = "static")]
+    static_: Option<StaticDetails>,
+    #[serde(rename = "struct")]
+    struct_: Option<StructDetails>,
+    struct_field: Option<Type>,
+    primitive: Option<PrimitiveDetails>,
+    #[serde(rename = "trait")]
+    trait_: Option<TraitDetails>,
+    type_alias: Option<TypeAliasDetails>,
+    trait_alias: Option<TraitAliasDetails>,
+    variant: Option<VariantDetails>,
+    /// The kinds of item which aren't supported yet, e.g. `opaque_ty`,
+    /// keyed by rustdoc's name for the kind. See
+    /// [`RustDoc::unsupported_kinds`].
+    #[serde(flatten)]
+    unsupported: HashMap<String, Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct EnumDetails {
+    generics: Generics,
     variants: Vec<String>,
     variants_stripped: bool,
+    impls: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct VariantDetails {
+    kind: VariantKind,
+    discriminant: Option<Discriminant>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum VariantKind {
+    /// `Variant`
+    Plain,
+    /// `Variant(u32)`. Hidden fields are `None`.
+    Tuple(Vec<Option<String>>),
+    /// `Variant { x: u32 }`
+    Struct {
+        fields: Vec<String>,
+        fields_stripped: bool,
+    },
+}
+
+/// An explicit enum discriminant, e.g. the `= 0x10` in `Variant = 0x10`.
+#[derive(Debug, Deserialize, Serialize)]
+struct Discriminant {
+    /// The expression as written, e.g. `0x10`.
+    expr: String,
+    /// The value in decimal, e.g. `16`.
+    value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct StructDetails {
+    kind: StructKind,
+    generics: Generics,
+    impls: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct EnumVariant {
+#[serde(rename_all = "snake_case")]
+enum StructKind {
+    /// `struct Unit;`
+    Unit,
+    /// `struct Tuple(u32);`. Private and hidden fields are `None`.
+    Tuple(Vec<Option<String>>),
+    /// `struct Plain { x: u32 }`
+    Plain {
+        fields: Vec<String>,
+        fields_stripped: bool,
+    },
+}
+
+/// A primitive type documented by `std` or `core`, e.g. `i32`.
+#[derive(Debug, Deserialize, Serialize)]
+struct PrimitiveDetails {
     name: String,
-    docs: Option<String>,
+    impls: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct FunctionDetails {
-    decl: FunctionDecl,
+struct ImplDetails {
+    generics: Generics,
+    #[serde(rename = "trait")]
+    trait_: Option<ResolvedPath>,
+    #[serde(rename = "for")]
+    for_: Type,
+    items: Vec<String>,
+    /// The generic type a blanket impl is for, e.g. the `T` in
+    /// `impl<T: Display> ToString for T`. `for_` is then the concrete type
+    /// rustdoc attributed this copy of the impl to.
+    blanket_impl: Option<Type>,
+    /// Whether rustdoc inferred this impl rather than it being written, as
+    /// for auto traits like `impl Send for Foo`.
+    #[serde(default)]
+    synthetic: bool,
+    /// Whether this is a negative impl, e.g. `impl !Send for Foo`.
+    #[serde(default)]
+    negative: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct FunctionDecl {
-    inputs: Vec<(String, Parameter)>,
-    output: Option<ReturnType>,
-    c_variadic: bool,
+struct ModuleDetails {
+    is_crate: bool,
+    #[serde(default)]
+    items: Vec<String>,
+    is_stripped: bool,
 }
 
+/// A `use` item, e.g. `pub use inner::Foo as Bar;`.
 #[derive(Debug, Deserialize, Serialize)]
-#[serde(untagged)]
-enum Parameter {
-    BorrowedRef { borrowed_ref: Box<BorrowedRefParam> },
-    Primitive { primitive: String },
-    Generic { generic: String },
-    ResolvedPath { resolved_path: Box<ResolvedPath> },
-    Qualified { qualified_path: Box<QualifiedPath> },
-    Slice { slice: Box<Parameter> },
-    Array { array: Box<ParameterArrayType> },
-    RawPointer { raw_pointer: Box<RawPointer> },
-    ImplTrait { impl_trait: Vec<ImplTrait> },
-    DynTrait { dyn_trait: Box<DynTrait> },
+struct ImportDetails {
+    /// The path as written, e.g. `inner::Foo`.
+    source: String,
+    /// The name the item is imported as, e.g. `Bar`.
+    name: String,
+    /// The id of the imported item, if rustdoc could resolve it.
+    id: Option<String>,
+    glob: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct DynTrait {
-    lifetime: Option<String>,
-    traits: Vec<TraitBound>,
+struct AssocConstDetails {
+    #[serde(rename = "type")]
+    type_: Type,
+    default: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct ParameterArrayType {
-    len: String,
+struct ConstantDetails {
     #[serde(rename = "type")]
-    type_: Box<Parameter>,
+    type_: Type,
+    #[serde(rename = "const")]
+    const_: ConstExpr,
 }
 
-// For function parameters:
 #[derive(Debug, Deserialize, Serialize)]
-struct BorrowedRefParam {
-    lifetime: Option<String>,
-    mutable: bool,
+struct AssocTypeDetails {
+    generics: Generics,
+    /// e.g. the `Clone` in `type Item: Clone;`
+    bounds: Vec<GenericBound>,
+    default: Option<Type>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TraitDetails {
+    is_auto: bool,
+    is_unsafe: bool,
+    items: Vec<String>,
+    generics: Generics,
+    /// Supertraits, e.g. the `Clone` in `trait Foo: Clone`
+    bounds: Vec<GenericBound>,
+    implementations: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TypeAliasDetails {
+    #[serde(rename = "type")]
+    type_: Type,
+    generics: Generics,
+}
+
+/// An unstable trait alias, e.g. `trait Tasks = Iterator + Send;`.
+#[derive(Debug, Deserialize, Serialize)]
+struct TraitAliasDetails {
+    generics: Generics,
+    /// The bounds on the right-hand side.
+    params: Vec<GenericBound>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FunctionDetails {
+    decl: FunctionDecl,
+    generics: Generics,
+    header: FunctionHeader,
+    has_body: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FunctionHeader {
+    #[serde(rename = "const")]
+    const_: bool,
+    #[serde(rename = "unsafe")]
+    unsafe_: bool,
+    #[serde(rename = "async")]
+    async_: bool,
+    abi: Abi,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+enum Abi {
+    Rust,
+    C { unwind: bool },
+    Cdecl { unwind: bool },
+    Stdcall { unwind: bool },
+    Fastcall { unwind: bool },
+    Aapcs { unwind: bool },
+    Win64 { unwind: bool },
+    SysV64 { unwind: bool },
+    System { unwind: bool },
+    Other(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct StaticDetails {
     #[serde(rename = "type")]
-    type_: Box<Parameter>,
+    type_: Type,
+    mutable: bool,
+    /// The initializer expression. Empty for statics in `extern` blocks.
+    expr: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FunctionDecl {
+    inputs: Vec<(String, Type)>,
+    output: Option<Type>,
+    c_variadic: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DynTrait {
+    lifetime: Option<String>,
+    traits: Vec<TraitBound>,
 }
 
-// For function return types:
 #[derive(Debug, Deserialize, Serialize)]
-struct BorrowedRefReturn {
+struct BorrowedRef {
     lifetime: Option<String>,
     mutable: bool,
     #[serde(rename = "type")]
-    type_: Box<ReturnType>,
+    type_: Box<Type>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -117,8 +803,8 @@ enum GenericArgs {
 
 #[derive(Debug, Deserialize, Serialize)]
 struct ParenthesizedGenericArgs {
-    inputs: Vec<Parameter>,
-    output: Option<Box<ReturnType>>,
+    inputs: Vec<Type>,
+    output: Option<Box<Type>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -146,15 +832,17 @@ struct TypeBinding {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 enum BindingKind {
+    // `Item = u32`
     Equality { equality: EqualityConstraint },
-    // Possibly other variants if needed, e.g. `Constraint { ... }`
+    // `Item: Display + 'static`
+    Constraint { constraint: Vec<GenericBound> },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct EqualityConstraint {
     // Rustdoc uses `"type"` for the equality’s right-hand side
     #[serde(rename = "type")]
-    type_: Box<ReturnType>,
+    type_: Box<Type>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -162,32 +850,35 @@ struct EqualityConstraint {
 enum GenericArg {
     Type {
         #[serde(rename = "type")]
-        type_inner: TypeContent,
+        type_: Box<Type>,
     },
     Lifetime {
         lifetime: String,
     },
+    Const {
+        #[serde(rename = "const")]
+        const_: ConstExpr,
+    },
 }
 
+/// A const generic arg or const item value, e.g. the `32` in `[u8; 32]`.
 #[derive(Debug, Deserialize, Serialize)]
-struct TypeContent {
-    primitive: Option<String>,
-    slice: Option<SliceContent>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct SliceContent {
-    primitive: String,
+struct ConstExpr {
+    expr: String,
+    value: Option<String>,
+    is_literal: bool,
 }
 
+/// A type wherever it's written, e.g. a fn's params and output, a field's
+/// type, or a generic arg. It's printed by [`format_type`].
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
-enum ReturnType {
+enum Type {
     ResolvedPath {
         resolved_path: Box<ResolvedPath>,
     },
     BorrowedRef {
-        borrowed_ref: Box<BorrowedRefReturn>,
+        borrowed_ref: Box<BorrowedRef>,
     },
     Primitive {
         primitive: String,
@@ -202,453 +893,7200 @@ enum ReturnType {
         array: Box<ArrayType>,
     },
     Tuple {
-        tuple: Vec<ReturnType>,
+        tuple: Vec<Type>,
     },
     Slice {
-        slice: Box<ReturnType>,
+        slice: Box<Type>,
     },
     RawPointer {
         raw_pointer: Box<RawPointer>,
     },
     ImplTrait {
-        impl_trait: Vec<ImplTrait>,
+        impl_trait: Vec<GenericBound>,
     },
-    // --- Add this variant ---
     DynTrait {
         dyn_trait: Box<DynTrait>,
     },
+    FunctionPointer {
+        function_pointer: Box<FunctionPointer>,
+    },
+    /// A type this crate doesn't support yet, e.g. a nightly-only `dyn*`,
+    /// kept as JSON so the rest of the item still parses. It's printed as a
+    /// placeholder; see [`format_unknown_type`]. This must stay last, as it
+    /// matches anything.
+    Unknown(Value),
+}
+
+/// A function pointer type, e.g. `fn(&str) -> bool`.
+#[derive(Debug, Deserialize, Serialize)]
+struct FunctionPointer {
+    decl: FunctionDecl,
+    generic_params: Vec<GenericParam>,
+    header: FunctionHeader,
+}
+
+/// A single bound in an `impl Trait` or generic bounds list, e.g. the
+/// `Iterator<Item = u32>` and `'a` in `impl Iterator<Item = u32> + 'a`.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum GenericBound {
+    TraitBound {
+        trait_bound: TraitBound,
+    },
+    Outlives {
+        outlives: String,
+    },
+    /// A precise capturing clause, e.g. `use<'a, T>`. Newer rustdocs emit
+    /// these for `impl Trait + use<'a, T>`.
+    Use {
+        #[serde(rename = "use")]
+        use_: Vec<CapturedParam>,
+    },
 }
 
+/// A param captured by a `use<..>` bound. Older formats list bare names,
+/// newer ones tag each with its kind.
 #[derive(Debug, Deserialize, Serialize)]
-struct ImplTrait {
-    trait_bound: TraitBound,
+#[serde(untagged)]
+enum CapturedParam {
+    Name(String),
+    Lifetime { lifetime: String },
+    Param { param: String },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct TraitBound {
     generic_params: Vec<GenericParam>,
     #[serde(default)]
-    modifier: Option<String>,
+    modifier: TraitBoundModifier,
     #[serde(rename = "trait")]
     trait_: ResolvedPath,
 }
 
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TraitBoundModifier {
+    #[default]
+    None,
+    /// `?Sized`
+    Maybe,
+    /// `~const Trait`
+    MaybeConst,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Generics {
+    params: Vec<GenericParam>,
+    where_predicates: Vec<WherePredicate>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum WherePredicate {
+    // `T: Clone + ?Sized`
+    Bound { bound_predicate: BoundPredicate },
+    // `'a: 'b`
+    Region { region_predicate: RegionPredicate },
+    // `<T as Iterator>::Item = u32`
+    Eq { eq_predicate: EqPredicate },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BoundPredicate {
+    #[serde(rename = "type")]
+    type_: Type,
+    bounds: Vec<GenericBound>,
+    /// The predicate's higher-ranked binder, e.g. the `'a` in
+    /// `for<'a> T: Fn(&'a str)`.
+    #[serde(default)]
+    generic_params: Vec<GenericParam>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RegionPredicate {
+    lifetime: String,
+    bounds: Vec<GenericBound>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct EqPredicate {
+    lhs: Type,
+    rhs: EqualityConstraint,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct GenericParam {
+    /// e.g. `'a`, `T`
+    name: String,
+    kind: GenericParamKind,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
-struct GenericParam {}
+#[serde(rename_all = "snake_case")]
+enum GenericParamKind {
+    Lifetime {
+        outlives: Vec<String>,
+    },
+    Type {
+        bounds: Vec<GenericBound>,
+        default: Option<Type>,
+        /// Whether the compiler introduced this param for an `impl Trait`
+        /// argument, in which case it doesn't appear in the source.
+        synthetic: bool,
+    },
+    Const {
+        #[serde(rename = "type")]
+        type_: Type,
+        default: Option<String>,
+    },
+}
 
 #[derive(Debug, Deserialize, Serialize)]
 struct ArrayType {
-    len: String,
+    len: ArrayLen,
     #[serde(rename = "type")]
-    type_: Box<ReturnType>,
+    type_: Box<Type>,
+}
+
+/// An array length, e.g. the `SIZE * 2` in `[u8; SIZE * 2]`.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum ArrayLen {
+    /// The length as written, or its value if it doesn't depend on generics.
+    Expr(String),
+    /// The length as written along with its value, if it could be evaluated.
+    Evaluated { expr: String, value: Option<String> },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct RawPointer {
     mutable: bool,
     #[serde(rename = "type")]
-    type_: Box<ReturnType>,
+    type_: Box<Type>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct QualifiedPath {
     name: String,
     args: Option<GenericArgs>,
-    self_type: Box<Parameter>,
+    self_type: Box<Type>,
+    #[serde(rename = "trait")]
     trait_: Option<ResolvedPath>,
 }
 
 // --- Implementations --- //
 
-impl RustDoc {
-    pub fn print(&self) {
-        println!("Crate Documentation");
-        println!("==================");
-        println!();
-        println!("Root: {}", self.root);
-        println!("Version: {}", self.crate_version);
-        println!("Includes private items: {}", self.includes_private);
-        println!();
-        println!("Items");
-        println!("-----");
-        println!();
-
-        for (id, item) in &self.index {
-            // Only print items from this crate (those starting with "0:")
-            if id.starts_with("0:") {
-                item.print(self);
-            }
-        }
+impl TryFrom<Value> for RustDoc {
+    type Error = serde_json::Error;
+
+    fn try_from(mut json: Value) -> Result<Self, Self::Error> {
+        resolve_path_names(&mut json);
+        let RustDocJson {
+            root,
+            crate_version,
+            includes_private,
+            index,
+            paths,
+        } = serde_json::from_value(json)?;
+        Ok(Self {
+            root,
+            crate_version,
+            includes_private,
+            index,
+            paths,
+            assoc_item_owners: OnceLock::new(),
+            field_owners: OnceLock::new(),
+        })
     }
 }
 
-impl RustDocItem {
-    fn print(&self, doc: &RustDoc) {
-        if let Some(name) = &self.name {
-            let Some(docs) = &self.docs else { return };
-            // NOTE: We might want to restrict to public items only.
-            // For now, we print everything.
-            // if self.visibility.as_deref() != Some("public") {
-            //     return;
-            // }
-
-            println!("---");
-            println!();
-            println!("`{name}`:");
-            println!();
-
-            if let Some(inner) = &self.inner {
-                if let Some(f) = &inner.function {
-                    f.decl.print(name);
-                    println!();
-                }
-                if let Some(enum_details) = &inner.enum_ {
-                    println!("```rust");
-                    println!("pub enum {name} {{");
-                    for variant_id in &enum_details.variants {
-                        if let Some(variant) = doc.index.get(variant_id) {
-                            if let Some(docs) = &variant.docs {
-                                println!("    /// {docs}");
-                            }
-                            if let Some(name) = &variant.name {
-                                println!("    {name},");
-                            }
-                        }
-                    }
-                    println!("}}");
-                    println!("```");
-                    println!();
+/// Fills in the `name` of every resolved path in `json`'s index which
+/// rustdoc left empty, e.g. for some re-exported items, so it isn't printed
+/// as an empty string. The name is the last segment of the id's entry in
+/// `paths`, else the name of the item in `index`, else `_`.
+///
+/// The trait of a qualified path is left alone, as an empty name there means
+/// the path was written without one, e.g. `T::Item`.
+fn resolve_path_names(json: &mut Value) {
+    fn visit(value: &mut Value, names: &HashMap<String, String>) {
+        match value {
+            Value::Object(object) if object.contains_key("self_type") => object
+                .iter_mut()
+                .filter(|(key, _)| *key != "trait")
+                .for_each(|(_, v)| visit(v, names)),
+            Value::Object(object) => {
+                let is_unnamed_path = object.contains_key("args")
+                    && object.get("name").and_then(Value::as_str) == Some("");
+                if is_unnamed_path {
+                    let name = object
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .and_then(|id| names.get(id))
+                        .map_or("_", String::as_str);
+                    object.insert("name".to_owned(), Value::from(name));
                 }
+                object.values_mut().for_each(|v| visit(v, names));
             }
-
-            println!("{docs}");
-            println!();
+            Value::Array(array) =>
+                array.iter_mut().for_each(|v| visit(v, names)),
+            _ => (),
         }
     }
+
+    let indexed_names = json["index"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(id, item)| Some((id, item["name"].as_str()?)));
+    let path_names =
+        json["paths"].as_object().into_iter().flatten().filter_map(
+            |(id, summary)| {
+                Some((id, summary["path"].as_array()?.last()?.as_str()?))
+            },
+        );
+    // Later entries win, so a path's name takes precedence.
+    let names = indexed_names
+        .chain(path_names)
+        .filter(|(_, name)| !name.is_empty())
+        .map(|(id, name)| (id.clone(), name.to_owned()))
+        .collect::<HashMap<_, _>>();
+    if let Some(index) = json.get_mut("index") {
+        visit(index, &names);
+    }
 }
 
-impl FunctionDecl {
-    fn print(&self, name: &str) {
-        print!("```rust\npub fn {name}(");
+impl RustDoc {
+    /// Parses rustdoc's JSON output from bytes, e.g. those of a file or from
+    /// [`include_bytes!`], without first checking they're valid UTF-8.
+    pub fn from_slice(json: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(json)
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+
+    /// The whole document with the default options, as printed by
+    /// [`RustDoc::print`], e.g. to hand to an LLM or write to a file.
+    pub fn render(&self) -> String {
+        // Only `strict` rendering can fail
+        self.render_with(&PrintOptions::default())
+            .expect("rendering with the default options never fails")
+    }
+
+    /// The whole document as printed by [`RustDoc::print_with`]. Unlike
+    /// [`RustDoc::write_markdown`], this renders [`OutputFormat::Json`] as
+    /// the JSON tree and [`OutputFormat::Ts`] as TypeScript declarations.
+    /// Fails as `write_markdown` does with [`PrintOptions::strict`].
+    pub fn render_with(&self, opts: &PrintOptions) -> io::Result<String> {
+        if let Some(rendered) = self.render_unstreamed(opts) {
+            return Ok(rendered);
+        }
+        let mut rendered = Vec::new();
+        self.write_markdown(&mut rendered, opts)?;
+        let rendered = String::from_utf8(rendered)
+            .expect("rendered from strings, so always UTF-8");
+        Ok(rendered)
+    }
 
-        let mut first = true;
-        for (param_name, param) in &self.inputs {
-            if !first {
-                print!(", ");
+    /// The whole document in the formats which aren't written item by item,
+    /// [`OutputFormat::Json`] and [`OutputFormat::Ts`]. Unlike markdown,
+    /// these can't fail, even with [`PrintOptions::strict`].
+    fn render_unstreamed(&self, opts: &PrintOptions) -> Option<String> {
+        match opts.format {
+            OutputFormat::Json => {
+                let tree_json = self.to_tree_json_with(opts);
+                // The alternate form is pretty-printed
+                Some(format!("{tree_json:#}\n"))
             }
-            print!("{param_name}: {param}");
-            first = false;
+            OutputFormat::Ts => Some(self.to_typescript(opts)),
+            _ => None,
         }
+    }
+
+    /// Summarize every item in this crate which has a path, filtered as in
+    /// markdown printing, e.g. by [`PrintOptions::public_only`].
+    pub fn summary(&self, opts: &PrintOptions) -> ApiSummary {
+        let items = self
+            .api_items()
+            .into_iter()
+            .filter(|(item, _)| is_selected(item, opts))
+            .map(|(_, api_item)| api_item)
+            .collect();
+        ApiSummary { items }
+    }
+
+    /// One [`EmbeddingDoc`] per public item, for feeding a vector store.
+    /// Items are filtered as in markdown printing, e.g. by
+    /// [`PrintOptions::include_kinds`].
+    pub fn to_embedding_docs(&self, opts: &PrintOptions) -> Vec<EmbeddingDoc> {
+        self.api_items()
+            .into_iter()
+            .filter(|(item, _)| item.visibility.as_deref() == Some("public"))
+            .filter(|(item, _)| is_selected(item, opts))
+            .map(|(_, api_item)| EmbeddingDoc::from(api_item))
+            .collect()
+    }
 
-        print!(")");
+    /// A hash of this crate's public API, for cheaply detecting whether it
+    /// changed between builds. Docs don't affect the hash.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of one line per public item, in
+    /// path order: `{kind} {path} {signature}\n`, where `kind` is as in
+    /// rustdoc's JSON (e.g. `function`) and `signature` is
+    /// [`ApiItem::signature`], or empty if there is none.
+    pub fn public_api_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
 
-        if let Some(ret) = &self.output {
-            print!(" -> {ret}");
+        let mut hash = FNV_OFFSET_BASIS;
+        for api_item in self.public_api_items() {
+            let kind = api_item.kind.to_string();
+            let path = &api_item.path;
+            let signature = api_item.signature.unwrap_or_default();
+            let line = format!("{kind} {path} {signature}\n");
+            for byte in line.bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
         }
+        hash
+    }
 
-        println!(";\n```");
+    /// The public items whose signatures differ from those of the items with
+    /// the same paths and kinds in `old`, an earlier version of this crate,
+    /// sorted by path. Items which were added or removed aren't included.
+    pub fn changed_signatures(&self, old: &RustDoc) -> Vec<ChangedSignature> {
+        let old_signatures = old
+            .public_api_items()
+            .filter_map(|api_item| {
+                let signature = api_item.signature?;
+                Some(((api_item.path, api_item.kind), signature))
+            })
+            .collect::<HashMap<_, _>>();
+        self.public_api_items()
+            .filter_map(|api_item| {
+                let after = api_item.signature?;
+                let key = (api_item.path, api_item.kind);
+                let before = old_signatures.get(&key)?;
+                let (path, kind) = key;
+                (*before != after).then(|| ChangedSignature {
+                    path,
+                    kind,
+                    before: before.clone(),
+                    after,
+                })
+            })
+            .collect()
     }
-}
 
-impl GenericArg {
-    fn format(&self) -> String {
-        match self {
-            Self::Type { type_inner } => {
-                if let Some(primitive) = &type_inner.primitive {
-                    primitive.clone()
-                } else if let Some(slice) = &type_inner.slice {
-                    format!("[{}]", slice.primitive)
-                } else {
-                    "/* unknown type */".to_string()
-                }
+    /// A "Changed Signatures" section showing the signature of each of the
+    /// [`RustDoc::changed_signatures`] before and after, e.g. for writing
+    /// migration notes. Returns an empty string if none changed.
+    pub fn format_changed_signatures(
+        &self,
+        old: &RustDoc,
+        opts: &PrintOptions,
+    ) -> String {
+        let changes = self.changed_signatures(old);
+        if changes.is_empty() {
+            return String::new();
+        }
+        let renderer = opts.format.renderer();
+        let mut out = format!("{}\n", renderer.section("Changed Signatures"));
+        for change in changes {
+            let heading = renderer.item_heading(&change.path);
+            out.push_str(&format!("{heading}\n\n"));
+            for (label, signature) in
+                [("Before", &change.before), ("After", &change.after)]
+            {
+                let code_block =
+                    format_code_fence(&format!("{signature}\n"), opts);
+                let labeled = renderer.labeled_block(label, &code_block);
+                out.push_str(&format!("{labeled}\n"));
             }
-            Self::Lifetime { lifetime } => lifetime.clone(),
         }
+        out
     }
-}
 
-impl fmt::Display for Parameter {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::BorrowedRef { borrowed_ref } => {
-                if let Some(lt) = &borrowed_ref.lifetime {
-                    write!(f, "&{} ", lt)?;
-                } else {
-                    write!(f, "&")?;
-                }
-                if borrowed_ref.mutable {
-                    write!(f, "mut ")?;
-                }
-                write!(f, "{}", borrowed_ref.type_)
-            }
-            Self::Primitive { primitive } => write!(f, "{}", primitive),
-            Self::Qualified { qualified_path } => {
-                write!(
-                    f,
-                    "{}{}",
-                    qualified_path.name,
-                    format_angle_bracketed_args(qualified_path.args.as_ref())
-                )
-            }
-            Self::Generic { generic } => write!(f, "{}", generic),
-            Self::ResolvedPath { resolved_path } => {
-                write!(
-                    f,
-                    "{}{}",
-                    resolved_path.name,
-                    format_angle_bracketed_args(resolved_path.args.as_ref())
-                )
-            }
-            Self::Slice { slice } => write!(f, "[{}]", slice),
-            Self::Array { array } => {
-                write!(f, "[{}; {}]", array.type_, array.len)
-            }
-            Self::RawPointer { raw_pointer } =>
-                if raw_pointer.mutable {
-                    write!(f, "*mut {}", raw_pointer.type_)
-                } else {
-                    write!(f, "*const {}", raw_pointer.type_)
-                },
-            Self::ImplTrait { impl_trait } => {
-                let bounds = impl_trait
-                    .iter()
-                    .map(|item| item.trait_bound.trait_.name.clone())
-                    .collect::<Vec<_>>()
-                    .join(" + ");
-                write!(f, "impl {}", bounds)
+    /// A "Types" section listing this crate's publicly reachable structs,
+    /// enums, unions, traits, and type and trait aliases, sorted by path,
+    /// with their kinds and doc summaries, e.g. ``- `inner::Foo` (struct):
+    /// Does foo.``. Types rendered with `opts` are linked to their entries.
+    /// Returns an empty string if there are no such types.
+    pub fn type_index(&self, opts: &PrintOptions) -> String {
+        const TYPE_KINDS: &[RustDocItemKind] = &[
+            RustDocItemKind::Struct,
+            RustDocItemKind::Enum,
+            RustDocItemKind::Union,
+            RustDocItemKind::Trait,
+            RustDocItemKind::TypeAlias,
+            RustDocItemKind::TraitAlias,
+        ];
+        let renderer = opts.format.renderer();
+        let entries = self
+            .api_items()
+            .into_iter()
+            .filter(|(item, api_item)| {
+                self.is_publicly_reachable(&item.id)
+                    && TYPE_KINDS.contains(&api_item.kind)
+                    && is_selected(item, opts)
+            })
+            .map(|(item, api_item)| {
+                let path =
+                    self.relative_path(&item.id).unwrap_or(api_item.path);
+                let name = format!("`{path}`");
+                let name = match self.anchor(&item.id) {
+                    Some(anchor) if self.is_rendered(&item.id, opts) =>
+                        renderer.link(&name, &anchor),
+                    _ => name,
+                };
+                let kind = api_item.kind;
+                let entry = match api_item.docs_summary {
+                    Some(summary) if !summary.is_empty() => {
+                        let summary = renderer.text(&summary);
+                        format!("{name} ({kind}): {summary}")
+                    }
+                    _ => format!("{name} ({kind})"),
+                };
+                renderer.list_item(&entry)
+            })
+            .collect::<String>();
+        if entries.is_empty() {
+            return String::new();
+        }
+        let section = renderer.section("Types");
+        let list = renderer.list(&entries);
+        format!("{section}\n{list}")
+    }
+
+    /// Every public local item which has a path, sorted by path.
+    fn public_api_items(&self) -> impl Iterator<Item = ApiItem> + '_ {
+        self.api_items()
+            .into_iter()
+            .filter(|(item, _)| item.visibility.as_deref() == Some("public"))
+            .map(|(_, api_item)| api_item)
+    }
+
+    /// Every local item which has a path, sorted by path.
+    fn api_items(&self) -> Vec<(&RustDocItem, ApiItem)> {
+        let mut items = self
+            .paths
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .filter_map(|(id, summary)| {
+                let item = self.index.get(id)?;
+                let api_item = ApiItem {
+                    path: summary.path.join("::"),
+                    kind: summary.kind,
+                    signature: item.signature(),
+                    docs_summary: item.docs.as_deref().map(docs_summary),
+                    deprecated: item.is_deprecated(),
+                };
+                Some((item, api_item))
+            })
+            .collect::<Vec<_>>();
+        items.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+        items
+    }
+
+    /// The crate's module tree as JSON, for front-ends which want to build a
+    /// collapsible tree view. Starting from the root module, each node has
+    /// the shape:
+    ///
+    /// ```json
+    /// {
+    ///   "name": "encode",
+    ///   "path": "hex::encode",
+    ///   "kind": "function",
+    ///   "signature": "pub fn encode(bytes: &[u8]) -> String;",
+    ///   "docs": "Convert a byte slice to an owned hex string. ...",
+    ///   "items": []
+    /// }
+    /// ```
+    ///
+    /// `signature` is the same definition printed in the markdown code block,
+    /// or `null` if none is available. Only modules have child `items`.
+    pub fn to_tree_json(&self) -> Value {
+        self.to_tree_json_with(&PrintOptions::default())
+    }
+
+    /// Like [`RustDoc::to_tree_json`], but with items filtered as in
+    /// markdown printing, e.g. by [`PrintOptions::public_only`]. Modules
+    /// which are filtered out themselves are kept if any of their items
+    /// aren't, so the tree still leads to them.
+    pub fn to_tree_json_with(&self, opts: &PrintOptions) -> Value {
+        self.tree_node_json(&self.root, opts).unwrap_or(Value::Null)
+    }
+
+    /// TypeScript declarations for the JSON serde's default derives would
+    /// produce for this crate's structs, enums and type aliases, plus
+    /// `declare`d consts, statics and fns. Docs become JSDoc comments, and
+    /// items are sorted by path and filtered as in markdown printing.
+    ///
+    /// Enums are externally tagged, e.g. `"Unit" | { Tuple: [number, string]
+    /// }`, `Option<T>` is `T | null`, and types with no obvious TypeScript
+    /// equivalent, like trait objects, are `unknown`.
+    pub fn to_typescript(&self, opts: &PrintOptions) -> String {
+        self.api_items()
+            .into_iter()
+            .filter(|(item, _)| is_selected(item, opts))
+            .filter_map(|(item, _)| {
+                let declaration = self.ts_declaration(item, opts)?;
+                let jsdoc = item.docs.as_deref().map(format_jsdoc);
+                Some(format!("{}{declaration}\n", jsdoc.unwrap_or_default()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The TypeScript declaration of a struct, enum, type alias, const,
+    /// static or fn. Other kinds of items have none.
+    fn ts_declaration(
+        &self,
+        item: &RustDocItem,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let name = item.name.as_deref()?;
+        let inner = item.inner.as_ref()?;
+        if let Some(struct_) = &inner.struct_ {
+            let params = format_ts_generic_params(&struct_.generics);
+            let type_ = self.ts_struct_type(&struct_.kind, opts);
+            let declaration = match struct_.kind {
+                StructKind::Plain { .. } =>
+                    format!("export interface {name}{params} {type_}"),
+                _ => format!("export type {name}{params} = {type_};"),
+            };
+            return Some(declaration);
+        }
+        if let Some(enum_) = &inner.enum_ {
+            let params = format_ts_generic_params(&enum_.generics);
+            let variants = enum_
+                .variants
+                .iter()
+                .filter_map(|id| self.index.get(id))
+                .filter_map(|variant| self.ts_variant_type(variant, opts))
+                .collect::<Vec<_>>();
+            let type_ = if variants.is_empty() {
+                "never".to_owned()
+            } else {
+                variants.join(" | ")
+            };
+            return Some(format!("export type {name}{params} = {type_};"));
+        }
+        if let Some(type_alias) = &inner.type_alias {
+            let params = format_ts_generic_params(&type_alias.generics);
+            let type_ = format_ts_type(&type_alias.type_);
+            return Some(format!("export type {name}{params} = {type_};"));
+        }
+        if let Some(constant) = &inner.constant {
+            let type_ = format_ts_type(&constant.type_);
+            return Some(format!("export declare const {name}: {type_};"));
+        }
+        if let Some(static_) = &inner.static_ {
+            let keyword = if static_.mutable { "let" } else { "const" };
+            let type_ = format_ts_type(&static_.type_);
+            return Some(format!("export declare {keyword} {name}: {type_};"));
+        }
+        if let Some(function) = &inner.function {
+            let params = format_ts_generic_params(&function.generics);
+            let inputs = function
+                .decl
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, (arg_name, type_))| {
+                    let type_ = format_ts_type(type_);
+                    match arg_name.as_str() {
+                        "_" | "" => format!("arg{i}: {type_}"),
+                        arg_name => format!("{arg_name}: {type_}"),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let output = function
+                .decl
+                .output
+                .as_ref()
+                .map_or_else(|| "void".to_owned(), format_ts_type);
+            let declaration = format!(
+                "export declare function {name}{params}({inputs}): {output};"
+            );
+            return Some(declaration);
+        }
+        None
+    }
+
+    /// The TypeScript type of a struct's fields, as serialized by serde: an
+    /// object type for named fields, the field's own type for a newtype, an
+    /// array for other tuple structs, and `null` for a unit struct. Fields
+    /// hidden by [`PrintOptions::public_only`] are left out.
+    fn ts_struct_type(&self, kind: &StructKind, opts: &PrintOptions) -> String {
+        match kind {
+            StructKind::Unit => "null".to_owned(),
+            StructKind::Tuple(fields) => self.ts_tuple_type(fields, opts),
+            StructKind::Plain { fields, .. } =>
+                self.ts_object_type(fields, opts),
+        }
+    }
+
+    /// A variant as serde externally tags it, e.g. `"Unit"` or
+    /// `{ Newtype: number }`.
+    fn ts_variant_type(
+        &self,
+        variant: &RustDocItem,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let name = variant.name.as_deref()?;
+        let details = variant.inner.as_ref()?.variant.as_ref()?;
+        let type_ = match &details.kind {
+            VariantKind::Plain => return Some(format!("\"{name}\"")),
+            VariantKind::Tuple(fields) => self.ts_tuple_type(fields, opts),
+            VariantKind::Struct { fields, .. } =>
+                self.ts_object_type(fields, opts),
+        };
+        Some(format!("{{ {name}: {type_} }}"))
+    }
+
+    /// The type of a newtype's only field, or an array type of the fields.
+    fn ts_tuple_type(
+        &self,
+        fields: &[Option<String>],
+        opts: &PrintOptions,
+    ) -> String {
+        let types = fields
+            .iter()
+            .flatten()
+            .filter_map(|id| self.ts_field(id, opts))
+            .map(|(_, type_)| type_)
+            .collect::<Vec<_>>();
+        match types.as_slice() {
+            [type_] if fields.len() == 1 => type_.clone(),
+            types => format!("[{}]", types.join(", ")),
+        }
+    }
+
+    /// An object type with a property per named field.
+    fn ts_object_type(&self, fields: &[String], opts: &PrintOptions) -> String {
+        let properties = fields
+            .iter()
+            .filter_map(|id| self.ts_field(id, opts))
+            .map(|(name, type_)| format!(" {name}: {type_};"))
+            .collect::<String>();
+        format!("{{{properties} }}")
+    }
+
+    /// The name and TypeScript type of the field with this id, unless it's
+    /// hidden by [`PrintOptions::public_only`].
+    fn ts_field(
+        &self,
+        id: &str,
+        opts: &PrintOptions,
+    ) -> Option<(&str, String)> {
+        let field =
+            self.index.get(id).filter(|field| field.is_visible(opts))?;
+        let type_ = field.inner.as_ref()?.struct_field.as_ref()?;
+        Some((field.name.as_deref()?, format_ts_type(type_)))
+    }
+
+    /// Item counts for each module in the crate, keyed by the module's path,
+    /// e.g. `fixtures::inner`. See [`ModuleStats`].
+    pub fn stats(&self) -> HashMap<String, ModuleStats> {
+        fn visit(
+            doc: &RustDoc,
+            tree: &ModuleTree,
+            path: String,
+            stats: &mut HashMap<String, ModuleStats>,
+        ) {
+            let mut module_stats = ModuleStats::default();
+            let mut count = |kind, has_docs: bool| {
+                *module_stats.kinds.entry(kind).or_default() += 1;
+                if !has_docs {
+                    *module_stats.undocumented.entry(kind).or_default() += 1;
+                }
+            };
+            for item in tree.item_ids.iter().filter_map(|id| doc.item(id)) {
+                if let Some(kind) = item.kind() {
+                    count(kind, item.docs.is_some());
+                }
             }
-            Self::DynTrait { dyn_trait } => {
-                let joined_traits = dyn_trait
-                    .traits
-                    .iter()
-                    .map(|tb| {
-                        let name = &tb.trait_.name;
-                        let args = format_angle_bracketed_args(
-                            tb.trait_.args.as_ref(),
-                        );
-                        format!("{name}{args}")
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" + ");
-                write!(f, "dyn {}", joined_traits)
+            for module in &tree.modules {
+                count(RustDocItemKind::Module, module.docs.is_some());
+                let name = &module.name;
+                visit(doc, module, format!("{path}::{name}"), stats);
             }
+            stats.insert(path, module_stats);
         }
+
+        let tree = self.module_tree();
+        let mut stats = HashMap::new();
+        visit(self, &tree, tree.name.clone(), &mut stats);
+        stats
     }
-}
 
-impl fmt::Display for ReturnType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Primitive { primitive } => write!(f, "{}", primitive),
-            Self::ResolvedPath { resolved_path } => {
-                write!(
-                    f,
-                    "{}{}",
-                    resolved_path.name,
-                    format_angle_bracketed_args(resolved_path.args.as_ref())
-                )
+    /// The crate's module hierarchy, starting from the root module. If the
+    /// root module is missing or has no items, the tree is just the root.
+    pub fn module_tree(&self) -> ModuleTree {
+        let root = self.index.get(&self.root);
+        self.module_subtree(self.crate_name(), root)
+    }
+
+    /// These docs with each item replaced by the result of `f`, or removed if
+    /// it returns `None`. References to removed items, e.g. from their
+    /// module, enum, or impl, or from `paths`, are removed too, so the result
+    /// is consistent and renders as if the items never existed.
+    pub fn filter_map_items(
+        self,
+        mut f: impl FnMut(RustDocItem) -> Option<RustDocItem>,
+    ) -> RustDoc {
+        let mut index = HashMap::new();
+        let mut removed = HashSet::new();
+        for (id, item) in self.index {
+            match f(item) {
+                Some(item) => {
+                    index.insert(id, item);
+                }
+                None => {
+                    removed.insert(id);
+                }
             }
-            Self::Array { array } => {
-                write!(f, "[{}; {}]", array.type_, array.len)
+        }
+        for item in index.values_mut() {
+            item.links.retain(|_dest, id| !removed.contains(id));
+            if let Some(inner) = &mut item.inner {
+                inner.remove_references(&removed);
             }
-            Self::BorrowedRef { borrowed_ref } => {
-                if let Some(lt) = &borrowed_ref.lifetime {
-                    write!(f, "&{} ", lt)?;
+        }
+        let mut paths = self.paths;
+        paths.retain(|id, _| !removed.contains(id));
+        RustDoc {
+            index,
+            paths,
+            assoc_item_owners: OnceLock::new(),
+            field_owners: OnceLock::new(),
+            ..self
+        }
+    }
+
+    /// The crate's name, from its root module, e.g. `hex`.
+    pub fn crate_name(&self) -> String {
+        let root = self.index.get(&self.root);
+        root.and_then(|root| root.name.clone())
+            .or_else(|| {
+                let summary = self.paths.get(&self.root)?;
+                Some(summary.path.join("::"))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Formats the YAML front matter block, e.g. `title: "hex"`. See
+    /// [`PrintOptions::front_matter`].
+    fn format_front_matter(&self, generated: SystemTime) -> String {
+        // JSON strings are valid YAML double-quoted strings, so this escapes
+        // any quotes or colons in the values
+        let title = Value::from(self.crate_name());
+        let crate_version = Value::from(self.crate_version.clone());
+        let generated = format_timestamp(generated);
+        format!(
+            "---\ntitle: {title}\ncrate_version: {crate_version}\n\
+             generated: {generated}\n---\n\n"
+        )
+    }
+
+    /// The document's title, e.g. `Crate hex v0.4.3`.
+    fn title(&self) -> String {
+        let name = self.crate_name();
+        // rustdoc doesn't record the crate's edition
+        match &self.crate_version {
+            Some(version) => format!("Crate {name} v{version}"),
+            None => format!("Crate {name}"),
+        }
+    }
+
+    /// The document's title and overview, e.g. `# Crate hex v0.4.3`.
+    fn format_header(&self, opts: &PrintOptions) -> String {
+        let renderer = opts.format.renderer();
+        let title = renderer.title(&self.title());
+        let includes_private = self.includes_private;
+        let overview = renderer.paragraph(
+            &renderer
+                .text(&format!("Includes private items: {includes_private}")),
+        );
+        format!("{title}\n{overview}")
+    }
+
+    /// The "Prelude" section, listing the re-exports of the local module
+    /// named [`PrintOptions::prelude_module`]. If several modules have that
+    /// name, the one closest to the crate root is used. Returns `None` if
+    /// there's no such module or it re-exports nothing.
+    fn format_prelude(&self, opts: &PrintOptions) -> Option<String> {
+        let module_name = opts.prelude_module.as_deref()?;
+        let (id, summary) = self
+            .paths
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .filter(|(_, summary)| {
+                summary.kind == RustDocItemKind::Module
+                    && summary.path.last().map(String::as_str)
+                        == Some(module_name)
+            })
+            .min_by(|(_, a), (_, b)| {
+                (a.path.len(), &a.path).cmp(&(b.path.len(), &b.path))
+            })?;
+        let module = self.index.get(id)?.inner.as_ref()?.module.as_ref()?;
+
+        let reexports = module
+            .items
+            .iter()
+            .filter_map(|id| {
+                self.index.get(id)?.inner.as_ref()?.import.as_ref()
+            })
+            .map(|import| {
+                let source = &import.source;
+                let name = &import.name;
+                if import.glob {
+                    format!("pub use {source}::*;\n")
+                } else if source.rsplit("::").next() == Some(name.as_str()) {
+                    format!("pub use {source};\n")
                 } else {
-                    write!(f, "&")?;
+                    format!("pub use {source} as {name};\n")
                 }
-                if borrowed_ref.mutable {
-                    write!(f, "mut ")?;
+            })
+            .collect::<String>();
+        if reexports.is_empty() {
+            return None;
+        }
+
+        let renderer = opts.format.renderer();
+        let section = renderer.section("Prelude");
+        let path = summary.path.join("::");
+        let intro = renderer.paragraph(
+            &renderer
+                .text(&format!("`use {path}::*;` brings these into scope:")),
+        );
+        let code_block = format_code_fence(&reexports, opts);
+        Some(format!("{section}\n{intro}{code_block}"))
+    }
+
+    /// Whether the item with the given id can be named from outside this
+    /// crate by its path, i.e. it and the modules containing it are all
+    /// `pub`. Items missing from the JSON, like hidden ones, aren't.
+    /// Re-exports under other paths aren't considered.
+    pub fn is_publicly_reachable(&self, id: &str) -> bool {
+        let is_public = |id: &str| {
+            self.index.get(id).is_some_and(|item| {
+                item.visibility.as_deref() == Some("public")
+            })
+        };
+        let Some(summary) = self.paths.get(id) else {
+            return false;
+        };
+        // The crate root is always reachable
+        is_public(id)
+            && (2..summary.path.len()).all(|len| {
+                let module_path = &summary.path[..len];
+                self.paths.iter().any(|(module_id, module)| {
+                    module.kind == RustDocItemKind::Module
+                        && module.path == module_path
+                        && is_public(module_id)
+                })
+            })
+    }
+
+    /// The name of the type or trait the associated item with the given id
+    /// belongs to, e.g. `Wrapper` for `Wrapper::new`.
+    fn assoc_item_owner(&self, id: &str) -> Option<&str> {
+        let (name, _id) = self.assoc_item_owners().get(id)?;
+        Some(name)
+    }
+
+    /// The id of the type or trait the associated item with the given id
+    /// belongs to, if that type has one, unlike e.g. `&str`.
+    fn assoc_item_owner_id(&self, id: &str) -> Option<&str> {
+        let (_name, owner_id) = self.assoc_item_owners().get(id)?;
+        owner_id.as_deref()
+    }
+
+    /// The map behind [`RustDoc::assoc_item_owner`], built on first use.
+    fn assoc_item_owners(&self) -> &HashMap<String, (String, Option<String>)> {
+        self.assoc_item_owners.get_or_init(|| {
+            let mut owners = HashMap::new();
+            let local_items = self
+                .index
+                .iter()
+                .filter(|(id, _)| id.starts_with("0:"))
+                .filter_map(|(_, item)| Some((item, item.inner.as_ref()?)));
+            for (item, inner) in local_items {
+                let (owner, item_ids) = match (&inner.impl_, &inner.trait_) {
+                    (Some(impl_), _) => {
+                        let owner_id = match &impl_.for_ {
+                            Type::ResolvedPath { resolved_path } =>
+                                resolved_path.id.clone(),
+                            _ => None,
+                        };
+                        ((impl_.self_type_name(), owner_id), &impl_.items)
+                    }
+                    (None, Some(trait_details)) => {
+                        let Some(name) = &item.name else { continue };
+                        let owner = (name.clone(), Some(item.id.clone()));
+                        (owner, &trait_details.items)
+                    }
+                    (None, None) => continue,
+                };
+                for item_id in item_ids {
+                    owners.insert(item_id.clone(), owner.clone());
                 }
-                write!(f, "{}", borrowed_ref.type_)
             }
-            Self::Tuple { tuple } =>
-                if tuple.is_empty() {
-                    write!(f, "()")
-                } else {
-                    write!(f, "(")?;
-                    for (i, t) in tuple.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{}", t)?;
+            owners
+        })
+    }
+
+    /// Whether the field with the given id has its docs printed in its
+    /// struct's definition, rather than getting its own entry, as named
+    /// fields of rendered structs do.
+    fn is_inlined_field(&self, id: &str, opts: &PrintOptions) -> bool {
+        let field_owners = self.field_owners.get_or_init(|| {
+            let mut owners = HashMap::new();
+            let local_structs = self
+                .index
+                .iter()
+                .filter(|(id, _)| id.starts_with("0:"))
+                .filter_map(|(id, item)| {
+                    Some((id, item.inner.as_ref()?.struct_.as_ref()?))
+                });
+            for (struct_id, struct_) in local_structs {
+                if let StructKind::Plain { fields, .. } = &struct_.kind {
+                    for field_id in fields {
+                        owners.insert(field_id.clone(), struct_id.clone());
                     }
-                    write!(f, ")")
-                },
-            Self::Generic { generic } => write!(f, "{}", generic),
-            Self::Qualified { qualified_path } => {
-                write!(
-                    f,
-                    "{}{}",
-                    qualified_path.name,
-                    format_angle_bracketed_args(qualified_path.args.as_ref())
-                )
-            }
-            Self::Slice { slice } => write!(f, "[{}]", slice),
-            Self::RawPointer { raw_pointer } =>
-                if raw_pointer.mutable {
-                    write!(f, "*mut {}", raw_pointer.type_)
-                } else {
-                    write!(f, "*const {}", raw_pointer.type_)
-                },
-            Self::ImplTrait { impl_trait } => {
-                let bounds = impl_trait
-                    .iter()
-                    .map(|item| item.trait_bound.trait_.name.clone())
-                    .collect::<Vec<_>>()
-                    .join(" + ");
-                write!(f, "impl {}", bounds)
+                }
             }
-            Self::DynTrait { dyn_trait } => {
-                let joined_traits = dyn_trait
-                    .traits
-                    .iter()
-                    .map(|tb| {
-                        let name = &tb.trait_.name;
-                        let args = format_angle_bracketed_args(
-                            tb.trait_.args.as_ref(),
-                        );
-                        format!("{name}{args}")
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" + ");
-                write!(f, "dyn {}", joined_traits)
+            owners
+        });
+        field_owners
+            .get(id)
+            .is_some_and(|struct_id| self.is_rendered(struct_id, opts))
+    }
+
+    /// The item with the given id, e.g. one of [`ModuleTree::item_ids`].
+    pub fn item(&self, id: &str) -> Option<&RustDocItem> {
+        self.index.get(id)
+    }
+
+    fn module_subtree(
+        &self,
+        name: String,
+        module: Option<&RustDocItem>,
+    ) -> ModuleTree {
+        let mut tree = ModuleTree {
+            name,
+            docs: module.and_then(|module| module.docs.clone()),
+            modules: Vec::new(),
+            item_ids: Vec::new(),
+        };
+        let child_ids = module
+            .and_then(|module| module.inner.as_ref()?.module.as_ref())
+            .map(|module| module.items.as_slice())
+            .unwrap_or_default();
+        for child_id in child_ids {
+            let child = self.index.get(child_id);
+            let is_module = child
+                .and_then(|child| child.inner.as_ref())
+                .is_some_and(|inner| inner.module.is_some());
+            match child.and_then(|child| child.name.clone()) {
+                Some(name) if is_module =>
+                    tree.modules.push(self.module_subtree(name, child)),
+                _ => tree.item_ids.push(child_id.clone()),
             }
         }
+        tree
     }
-}
 
-fn format_angle_bracketed_args(args: Option<&GenericArgs>) -> String {
-    match args {
-        None => String::new(),
-        Some(GenericArgs::AngleBracketed { angle_bracketed }) => {
-            let formatted_args = angle_bracketed
-                .args
+    fn tree_node_json(&self, id: &str, opts: &PrintOptions) -> Option<Value> {
+        let item = self.index.get(id)?;
+        let inner = item.inner.as_ref()?;
+        let summary = self.paths.get(id);
+
+        let (name, kind) = match (&inner.import, summary) {
+            (Some(import), _) => (import.name.clone(), RustDocItemKind::Import),
+            (None, Some(summary)) => (item.name.clone()?, summary.kind),
+            (None, None) => return None,
+        };
+        let path = summary.map(|summary| summary.path.join("::"));
+        let signature = item
+            .format_definition(&name, self, opts)
+            .map(|definition| definition.trim_end().to_owned());
+        let items = match &inner.module {
+            Some(module) => module
+                .items
                 .iter()
-                .map(|arg| arg.format())
-                .collect::<Vec<_>>();
-            if formatted_args.is_empty() {
-                String::new()
-            } else {
-                format!("<{}>", formatted_args.join(", "))
-            }
+                .filter_map(|child_id| self.tree_node_json(child_id, opts))
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        let is_root = id == self.root;
+        if !is_root && !is_selected(item, opts) && items.is_empty() {
+            return None;
         }
-        Some(GenericArgs::Parenthesized { parenthesized: _ }) => {
-            String::new()
-            // TODO(max): If we want to print them, do something like:
-            // format!("({}...)", ...)
+
+        Some(json!({
+            "name": name,
+            "path": path,
+            "kind": kind,
+            "signature": signature,
+            "docs": item.docs,
+            "items": items,
+        }))
+    }
+
+    /// Whether the item with the given id gets its own entry when printed
+    /// with `opts`. This must agree with [`RustDocItem::to_markdown`].
+    fn is_rendered(&self, id: &str, opts: &PrintOptions) -> bool {
+        id.starts_with("0:")
+            && self.index.get(id).is_some_and(|item| {
+                is_selected(item, opts)
+                    && item.name.is_some()
+                    && item.docs.is_some()
+            })
+            && !self.is_inlined_field(id, opts)
+    }
+
+    /// The in-document anchor for the item with the given id, derived from
+    /// its crate-relative path, e.g. `innermul` for `my_crate::inner::mul`.
+    /// Items without a path, like methods, fall back to their name.
+    fn anchor(&self, id: &str) -> Option<String> {
+        let name = match self.paths.get(id) {
+            Some(summary) if summary.path.len() > 1 =>
+                summary.path[1..].join(""),
+            Some(summary) => summary.path.join(""),
+            None => self.index.get(id)?.name.clone()?,
+        };
+        let anchor = name
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .flat_map(char::to_lowercase)
+            .collect();
+        Some(anchor)
+    }
+
+    /// The path of the module containing the item with the given id, e.g.
+    /// `my_crate::inner` for `my_crate::inner::mul`. Unlike the item's own
+    /// path, this excludes its name, so items can be grouped by module.
+    /// `None` for the crate root, which has no containing module, and for
+    /// items which aren't in `paths`, like methods.
+    pub fn module_path_of(&self, id: &str) -> Option<String> {
+        let summary = self.paths.get(id)?;
+        let (_name, module_path) = summary.path.split_last()?;
+        if module_path.is_empty() {
+            return None;
         }
+        Some(module_path.join("::"))
     }
-}
 
-#[cfg(test)]
-mod test {
-    use serde_json::Value;
+    /// The crate-relative path of the item with the given id, e.g.
+    /// `inner::mul` for `my_crate::inner::mul`, if it's in `paths`.
+    fn relative_path(&self, id: &str) -> Option<String> {
+        let summary = self.paths.get(id)?;
+        let relative = summary.path.get(1..).filter(|path| !path.is_empty());
+        Some(relative.unwrap_or(&summary.path).join("::"))
+    }
 
-    use super::*;
+    pub fn print_with(&self, opts: &PrintOptions) {
+        if let Some(rendered) = self.render_unstreamed(opts) {
+            print!("{rendered}");
+            return;
+        }
 
-    const COMMON_JSON_STR: &str =
-        include_str!("../test-data/common/rustdoc.json");
-    const HEX_JSON_STR: &str = include_str!("../test-data/hex/rustdoc.json");
+        // Streamed rather than rendered first, as crates can be huge
+        let mut stdout = io::stdout().lock();
+        // Like `println!`, panic if stdout was closed
+        self.write_markdown(&mut stdout, opts)
+            .expect("failed printing to stdout");
+    }
 
-    /// ```bash
-    /// $ cargo test print_hex_docs -- --ignored --nocapture
-    /// ```
-    #[test]
-    #[ignore]
-    fn print_hex_docs() {
-        // Parse into RustDoc struct first
-        let rust_doc = serde_json::from_str::<RustDoc>(HEX_JSON_STR).unwrap();
+    /// Writes the whole document as printed by [`RustDoc::print_with`].
+    /// [`OutputFormat::Json`] and [`OutputFormat::Ts`] are written as
+    /// markdown; use [`RustDoc::to_tree_json`] for the JSON tree and
+    /// [`RustDoc::to_typescript`] for the declarations.
+    pub fn write_markdown<W: io::Write>(
+        &self,
+        w: &mut W,
+        opts: &PrintOptions,
+    ) -> io::Result<()> {
+        self.write_markdown_with_progress(w, opts, |_, _| {})
+    }
 
-        // Also parse as generic JSON for raw printing
-        let full_json = serde_json::from_str::<Value>(HEX_JSON_STR).unwrap();
-        let index_json =
-            full_json.get("index").and_then(Value::as_object).unwrap();
+    /// Like [`RustDoc::write_markdown`], but calls `on_item(done, total)`
+    /// after each item is written, e.g. to show a progress bar. Each item is
+    /// written as soon as it's rendered, so the document is never held in
+    /// memory at once. Items are written in the [`PrintOptions::sort`] order,
+    /// with items declared in `extern` blocks grouped at the end.
+    pub fn write_markdown_with_progress<W: io::Write>(
+        &self,
+        w: &mut W,
+        opts: &PrintOptions,
+        mut on_item: impl FnMut(usize, usize),
+    ) -> io::Result<()> {
+        let renderer = opts.format.renderer();
+        // Only print items from this crate (those starting with "0:")
+        let mut items = self
+            .index
+            .iter()
+            .filter(|(id, item)| {
+                id.starts_with("0:") && is_selected(item, opts)
+            })
+            .map(|(_, item)| item)
+            .collect::<Vec<_>>();
+        match opts.sort {
+            // Items without spans, which shouldn't occur locally, go last
+            SortMode::Source => items.sort_by_key(|&item| {
+                let position =
+                    item.span.as_ref().map(|span| (&span.filename, span.begin));
+                (position.is_none(), position, &item.id)
+            }),
+            // Associated items are sorted by their type or trait, then by
+            // their own name. Modules are compared by segment, so each
+            // one's submodules follow it directly.
+            SortMode::Structured => items.sort_by_key(|&item| {
+                let owner = self
+                    .assoc_item_owner_id(&item.id)
+                    .and_then(|owner_id| self.index.get(owner_id));
+                let (ranked, assoc_name) = match owner {
+                    Some(owner) => (owner, Some(&item.name)),
+                    None => (item, None),
+                };
+                let module_path = self
+                    .paths
+                    .get(&ranked.id)
+                    .and_then(|summary| summary.path.split_last())
+                    .map(|(_name, module_path)| module_path);
+                let kind_rank = SortMode::kind_rank(ranked.kind());
+                (module_path, kind_rank, &ranked.name, assoc_name, &item.id)
+            }),
+        }
+        let (foreign_items, items) = items
+            .into_iter()
+            .partition::<Vec<_>, _>(|item| item.is_foreign());
 
-        // Print all items from this crate using RustDoc's index
-        for (id, item) in &rust_doc.index {
-            if id.starts_with("0:") {
-                println!();
-                println!("=== Item ID: {id} ===");
-                println!();
+        if opts.front_matter {
+            write!(w, "{}", self.format_front_matter(SystemTime::now()))?;
+        }
+        let toc = items
+            .iter()
+            .chain(&foreign_items)
+            .filter(|item| self.is_rendered(&item.id, opts))
+            .filter_map(|item| {
+                Some((item.name.as_deref()?, self.anchor(&item.id)?))
+            })
+            .collect::<Vec<_>>();
+        write!(w, "{}", renderer.document_start(&self.title(), &toc))?;
+        write!(w, "{}", self.format_header(opts))?;
+        if let Some(prelude) = self.format_prelude(opts) {
+            writeln!(w, "{prelude}")?;
+        }
+        writeln!(w, "{}", renderer.section("Items"))?;
 
-                // Get the raw JSON for this item
-                if let Some(item_json) = index_json.get(id) {
-                    println!("--- Raw JSON ---");
-                    let item_json_pretty =
-                        serde_json::to_string_pretty(item_json).unwrap();
-                    println!("{item_json_pretty}");
-                    println!();
+        let total = items.len() + foreign_items.len();
+        let mut done = 0;
+        let to_markdown = |item: &RustDocItem| {
+            item.try_to_markdown(self, opts)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        };
+        for item in items {
+            if let Some(markdown) = to_markdown(item)? {
+                write!(w, "{markdown}")?;
+            }
+            done += 1;
+            on_item(done, total);
+        }
+
+        if !foreign_items.is_empty() {
+            writeln!(w, "{}", renderer.section("Extern Blocks"))?;
+            for item in foreign_items {
+                if let Some(markdown) = to_markdown(item)? {
+                    write!(w, "{markdown}")?;
                 }
+                done += 1;
+                on_item(done, total);
+            }
+        }
 
-                println!("--- Formatted Output ---");
-                item.print(&rust_doc);
-                println!("=== End Item ===");
+        if opts.blanket_impls {
+            let blanket_impls = self.format_blanket_impls(opts);
+            if !blanket_impls.is_empty() {
+                let section = renderer.section("Blanket Implementations");
+                writeln!(w, "{section}")?;
+                let code_block = format_code_fence(&blanket_impls, opts);
+                writeln!(w, "{code_block}")?;
+            }
+        }
+
+        write!(w, "{}", renderer.document_end())?;
+
+        if opts.warn_dropped {
+            for (id, reason) in self.dropped_items_with_reasons(opts) {
+                eprintln!("warning: dropped item {id}: {reason}");
             }
         }
+        Ok(())
     }
 
-    /// This test is designed for quickly debugging parsing errors.
-    ///
-    /// # Workflow
-    ///
-    /// ```bash
-    /// $ just iterate cargo test test_parse_individual
-    /// ```
-    ///
-    /// Query an advanced GPT with something like:
-    ///
-    /// ```
-    /// I'm working on a crate which parse rustdoc outputs. There's one item
-    /// it's getting stuck on, caught in the tests. There may be a bug
-    /// somewhere. Here's the code and test output which I think should contain
-    /// enough info for you to be able to diagnose the issue. Can you help? If
-    /// you spot any fixes, please indicate which sections should be modified
-    /// along with the exact code that it should be modified to. Don't forget to
-    /// update the `Display` implementations if a new enum variant was added.
-    ///
-    /// <code>
+    /// The ids of the items whose definitions changed since the git ref
+    /// `since`, including uncommitted changes, for use as
+    /// [`PrintOptions::item_ids`]. `source_dir` must be the directory
+    /// `rustdoc` was run in, as the items' spans are relative to it.
     ///
-    /// <failed test output>
-    /// ```
-    #[test]
-    fn test_parse_individual() {
-        do_test(HEX_JSON_STR);
-        do_test(COMMON_JSON_STR);
-
-        fn do_test(json_str: &str) {
-            // First parse as generic JSON
-            let json = serde_json::from_str::<Value>(json_str).unwrap();
+    /// An item has changed if any line of its definition was added or
+    /// modified. Its doc comment isn't part of its definition.
+    pub fn changed_since(
+        &self,
+        source_dir: &Path,
+        since: &str,
+    ) -> io::Result<HashSet<String>> {
+        let diff =
+            duct::cmd!("git", "diff", "--unified=0", "--relative", since)
+                .dir(source_dir)
+                .read()?;
+        let changed_lines = parse_changed_lines(&diff);
+        Ok(self.items_in_changed_lines(&changed_lines))
+    }
 
-            // Get the index object
-            let index = json
-                .get("index")
-                .and_then(Value::as_object)
-                .expect("Expected 'index' to be an object");
+    /// The ids of the local items whose spans overlap `changed_lines`.
+    fn items_in_changed_lines(
+        &self,
+        changed_lines: &HashMap<String, Vec<(usize, usize)>>,
+    ) -> HashSet<String> {
+        self.index
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .filter(|(_, item)| {
+                let Some(span) = &item.span else { return false };
+                let Some(ranges) = changed_lines.get(&span.filename) else {
+                    return false;
+                };
+                ranges.iter().any(|(first, last)| {
+                    *first <= span.end.0 && span.begin.0 <= *last
+                })
+            })
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
 
-            let mut num_parsed = 0;
+    /// Formats the distinct blanket impls which apply to this crate's types,
+    /// sorted. rustdoc copies each blanket impl to every type it applies to.
+    fn format_blanket_impls(&self, opts: &PrintOptions) -> String {
+        let mut blanket_impls = self
+            .index
+            .values()
+            .filter_map(|item| item.inner.as_ref()?.impl_.as_ref())
+            .filter(|impl_| impl_.is_blanket())
+            .map(|impl_| impl_.format(self, opts, opts.expand_impl_items))
+            .collect::<Vec<_>>();
+        blanket_impls.sort_unstable();
+        blanket_impls.dedup();
+        blanket_impls.concat()
+    }
 
-            // Try to parse each item
-            for (id, item_value) in index {
-                match serde_json::from_value::<RustDocItem>(item_value.clone())
-                {
-                    Ok(_) => num_parsed += 1,
-                    Err(e) => {
-                        eprintln!("Failed to parse item {id}: {e}");
-                        eprintln!(
-                            "JSON: {}",
-                            serde_json::to_string_pretty(item_value).unwrap()
-                        );
-                        panic!("Failed after {num_parsed} parsed items");
+    /// The ids of the item at `path` and of every local type it depends on,
+    /// e.g. a function followed by the types of its params and return value,
+    /// and in turn those of their fields. Items are listed in the order
+    /// they're found, so the closest dependencies come first. Empty if
+    /// nothing is at `path`.
+    pub fn reachable_from(&self, path: &str) -> Vec<String> {
+        let mut queue = self
+            .find_by_path(path)
+            .into_iter()
+            .map(|item| item.id.as_str())
+            .collect::<VecDeque<_>>();
+        let mut visited = HashSet::new();
+        let mut reachable = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(item) = self.index.get(id) else {
+                continue;
+            };
+            reachable.push(id.to_owned());
+            self.visit_definition_paths(item, &mut |path| {
+                if let Some(id) = path.id.as_deref() {
+                    if id.starts_with("0:") {
+                        queue.push_back(id);
                     }
                 }
-            }
+            });
         }
+        reachable
     }
 
-    #[test]
-    fn test_parse_all() {
-        let doc = serde_json::from_str::<RustDoc>(HEX_JSON_STR).unwrap();
-        doc.print();
+    /// Calls `visit` with each path in `item`'s definition, e.g. in a
+    /// function's signature, a struct's fields or an enum's variants.
+    fn visit_definition_paths<'a>(
+        &'a self,
+        item: &'a RustDocItem,
+        visit: &mut dyn FnMut(&'a ResolvedPath),
+    ) {
+        let Some(inner) = &item.inner else { return };
+        // The ids of this item's fields or variants, whose own paths are
+        // part of this definition
+        let mut child_ids = Vec::new();
+        if let Some(function) = &inner.function {
+            function.decl.visit_paths(visit);
+            function.generics.visit_paths(visit);
+        }
+        if let Some(struct_) = &inner.struct_ {
+            struct_.generics.visit_paths(visit);
+            match &struct_.kind {
+                StructKind::Unit => {}
+                StructKind::Tuple(fields) =>
+                    child_ids.extend(fields.iter().flatten()),
+                StructKind::Plain { fields, .. } => child_ids.extend(fields),
+            }
+        }
+        if let Some(enum_) = &inner.enum_ {
+            enum_.generics.visit_paths(visit);
+            child_ids.extend(&enum_.variants);
+        }
+        if let Some(variant) = &inner.variant {
+            match &variant.kind {
+                VariantKind::Plain => {}
+                VariantKind::Tuple(fields) =>
+                    child_ids.extend(fields.iter().flatten()),
+                VariantKind::Struct { fields, .. } => child_ids.extend(fields),
+            }
+        }
+        for child in child_ids.into_iter().filter_map(|id| self.index.get(id)) {
+            self.visit_definition_paths(child, visit);
+        }
+        if let Some(type_) = &inner.struct_field {
+            type_.visit_paths(visit);
+        }
+        if let Some(type_alias) = &inner.type_alias {
+            type_alias.type_.visit_paths(visit);
+            type_alias.generics.visit_paths(visit);
+        }
+        if let Some(constant) = &inner.constant {
+            constant.type_.visit_paths(visit);
+        }
+        if let Some(static_) = &inner.static_ {
+            static_.type_.visit_paths(visit);
+        }
+    }
+
+    /// This crate's impls of the trait with the given id, e.g. `impl MyTrait
+    /// for A` and `impl MyTrait for B`, sorted by their signatures.
+    pub fn implementors(&self, trait_id: &str) -> Vec<&RustDocItem> {
+        let mut implementors =
+            self.index
+                .iter()
+                .filter(|(id, _)| id.starts_with("0:"))
+                .filter(|(_, item)| {
+                    let trait_ = item.inner.as_ref().and_then(|inner| {
+                        inner.impl_.as_ref()?.trait_.as_ref()
+                    });
+                    trait_.is_some_and(|trait_| {
+                        trait_.id.as_deref() == Some(trait_id)
+                    })
+                })
+                .map(|(_, item)| (item.signature().unwrap_or_default(), item))
+                .collect::<Vec<_>>();
+        implementors.sort_by(|(a, _), (b, _)| a.cmp(b));
+        implementors.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// How many items of each kind this crate's JSON has which aren't
+    /// supported yet, keyed by rustdoc's name for the kind, e.g.
+    /// `opaque_ty`. Such items are skipped when printing.
+    pub fn unsupported_kinds(&self) -> HashMap<String, usize> {
+        let mut kinds = HashMap::new();
+        let unsupported = self
+            .index
+            .values()
+            .filter_map(|item| item.inner.as_ref())
+            .flat_map(|inner| inner.unsupported.keys());
+        for kind in unsupported {
+            *kinds.entry(kind.clone()).or_default() += 1;
+        }
+        kinds
+    }
+
+    /// The local items with the given fully-qualified path, e.g.
+    /// `hex::encode`. Items which aren't in rustdoc's `paths` map, like
+    /// methods, are found by the path's last segment via
+    /// [`Self::find_by_name`].
+    pub fn find_by_path(&self, path: &str) -> Vec<&RustDocItem> {
+        let mut items = self
+            .paths
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .filter(|(_, summary)| summary.path.join("::") == path)
+            .filter_map(|(id, _)| self.index.get(id))
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            let name = path.rsplit("::").next().unwrap_or(path);
+            return self.find_by_name(name);
+        }
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        items
+    }
+
+    /// The local items with the given name, sorted by id.
+    pub fn find_by_name(&self, name: &str) -> Vec<&RustDocItem> {
+        let mut items = self
+            .index
+            .values()
+            .filter(|item| item.id.starts_with("0:"))
+            .filter(|item| item.name.as_deref() == Some(name))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        items
+    }
+
+    /// The markdown entry for the single item at `path`, resolved with
+    /// [`Self::find_by_path`].
+    pub fn item_markdown(
+        &self,
+        path: &str,
+        opts: &PrintOptions,
+    ) -> Result<String, LookupError> {
+        let item = match self.find_by_path(path).as_slice() {
+            [] => return Err(LookupError::NotFound(path.to_owned())),
+            [item] => *item,
+            candidates => {
+                let candidates =
+                    candidates.iter().map(|item| self.describe(item)).collect();
+                return Err(LookupError::Ambiguous {
+                    path: path.to_owned(),
+                    candidates,
+                });
+            }
+        };
+        item.to_markdown(self, opts)
+            .ok_or_else(|| LookupError::NotRendered(path.to_owned()))
+    }
+
+    /// Describes an item for disambiguation, e.g. `function hex::encode`, or
+    /// `map (0:12:345)` for items without a path.
+    fn describe(&self, item: &RustDocItem) -> String {
+        match self.paths.get(&item.id) {
+            Some(summary) => {
+                let kind = summary.kind;
+                let path = summary.path.join("::");
+                format!("{kind} {path}")
+            }
+            None => {
+                let name = item.name.as_deref().unwrap_or_default();
+                let id = &item.id;
+                format!("{name} ({id})")
+            }
+        }
+    }
+
+    /// The ids of the items in this crate which aren't printed with `opts`
+    /// because they have no name or docs, sorted by id. Items printed as
+    /// part of their parent, like impls and enum variants, are excluded.
+    pub fn dropped_items(&self, opts: &PrintOptions) -> Vec<String> {
+        self.dropped_items_with_reasons(opts)
+            .into_iter()
+            .map(|(id, _reason)| id.to_owned())
+            .collect()
+    }
+
+    fn dropped_items_with_reasons(
+        &self,
+        opts: &PrintOptions,
+    ) -> Vec<(&str, &'static str)> {
+        // The associated items of impls are printed with their impl
+        let impl_items = self
+            .index
+            .values()
+            .filter_map(|item| item.inner.as_ref()?.impl_.as_ref())
+            .flat_map(|impl_| &impl_.items)
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+
+        let mut dropped = self
+            .index
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .filter(|(id, _)| !impl_items.contains(id.as_str()))
+            .filter_map(|(id, item)| {
+                let reason = item.drop_reason(self, opts)?;
+                Some((id.as_str(), reason))
+            })
+            .collect::<Vec<_>>();
+        dropped.sort_unstable();
+        dropped
+    }
+}
+
+impl ModuleStats {
+    /// How many items the module contains.
+    pub fn total(&self) -> usize {
+        self.kinds.values().sum()
+    }
+
+    /// How many items the module contains without docs.
+    pub fn total_undocumented(&self) -> usize {
+        self.undocumented.values().sum()
+    }
+}
+
+impl RustDocItem {
+    /// Whether this is a fn or static declared in an `extern` block.
+    fn is_foreign(&self) -> bool {
+        let Some(inner) = &self.inner else {
+            return false;
+        };
+        let foreign_fn =
+            inner.function.as_ref().is_some_and(|f| f.is_foreign());
+        let foreign_static =
+            inner.static_.as_ref().is_some_and(|s| s.expr.is_empty());
+        foreign_fn || foreign_static
+    }
+
+    /// Whether this item is printed with [`PrintOptions::public_only`].
+    fn is_visible(&self, opts: &PrintOptions) -> bool {
+        !opts.public_only
+            || matches!(self.visibility.as_deref(), Some("public" | "default"))
+    }
+
+    /// Whether this item is marked `#[deprecated]`.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation.is_some()
+    }
+
+    /// What kind of item this is, e.g. [`RustDocItemKind::Function`] for a
+    /// fn or method.
+    pub fn kind(&self) -> Option<RustDocItemKind> {
+        let inner = self.inner.as_ref()?;
+        let kind = match inner {
+            _ if inner.function.is_some() => RustDocItemKind::Function,
+            _ if inner.enum_.is_some() => RustDocItemKind::Enum,
+            _ if inner.impl_.is_some() => RustDocItemKind::Impl,
+            _ if inner.assoc_const.is_some() => RustDocItemKind::AssocConst,
+            _ if inner.assoc_type.is_some() => RustDocItemKind::AssocType,
+            _ if inner.constant.is_some() => RustDocItemKind::Constant,
+            _ if inner.import.is_some() => RustDocItemKind::Import,
+            _ if inner.module.is_some() => RustDocItemKind::Module,
+            _ if inner.static_.is_some() => RustDocItemKind::Static,
+            _ if inner.struct_.is_some() => RustDocItemKind::Struct,
+            _ if inner.struct_field.is_some() => RustDocItemKind::StructField,
+            _ if inner.primitive.is_some() => RustDocItemKind::Primitive,
+            _ if inner.trait_.is_some() => RustDocItemKind::Trait,
+            _ if inner.type_alias.is_some() => RustDocItemKind::TypeAlias,
+            _ if inner.trait_alias.is_some() => RustDocItemKind::TraitAlias,
+            _ if inner.variant.is_some() => RustDocItemKind::Variant,
+            // rustdoc's names for the unsupported kinds match ours
+            _ => {
+                let key = inner.unsupported.keys().next()?;
+                serde_json::from_value(Value::String(key.clone())).ok()?
+            }
+        };
+        Some(kind)
+    }
+
+    /// Why this item isn't printed, if it should be but has no name or docs.
+    fn drop_reason(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<&'static str> {
+        let inner = self.inner.as_ref()?;
+        let printed_with_parent = inner.impl_.is_some()
+            || inner.struct_field.is_some()
+            || doc.paths.get(&self.id).is_some_and(|summary| {
+                summary.kind == RustDocItemKind::Variant
+            });
+        if printed_with_parent {
+            return None;
+        }
+
+        let target = match &inner.import {
+            Some(_) if !opts.inline_reexports => return Some("no name"),
+            Some(import) => match import.resolve(doc) {
+                Some((target, _source)) => target,
+                None => return Some("glob or unresolved import"),
+            },
+            None if self.name.is_none() => return Some("no name"),
+            None => self,
+        };
+        if target.docs.is_none() {
+            return Some("no docs");
+        }
+        None
+    }
+
+    /// Whether this is a `#[non_exhaustive]` struct with public fields, which
+    /// look like they could be set with a struct literal but can't be.
+    fn is_non_exhaustive_struct(&self, doc: &RustDoc) -> bool {
+        let Some(struct_details) =
+            self.inner.as_ref().and_then(|inner| inner.struct_.as_ref())
+        else {
+            return false;
+        };
+        let field_ids = match &struct_details.kind {
+            StructKind::Unit => return false,
+            StructKind::Tuple(fields) =>
+                fields.iter().flatten().collect::<Vec<_>>(),
+            StructKind::Plain { fields, .. } =>
+                fields.iter().collect::<Vec<_>>(),
+        };
+        let has_public_field = field_ids.into_iter().any(|id| {
+            doc.index
+                .get(id)
+                .is_some_and(|field| !field.visibility_prefix().is_empty())
+        });
+        let non_exhaustive =
+            self.attrs.iter().any(|attr| attr == "#[non_exhaustive]");
+        non_exhaustive && has_public_field
+    }
+
+    /// This item's attributes which are in [`ATTR_ALLOWLIST`].
+    fn allowed_attrs(&self) -> impl Iterator<Item = &str> {
+        self.attrs
+            .iter()
+            .map(String::as_str)
+            .filter(|attr| is_allowed_attr(attr))
+    }
+
+    /// The item's id, which is unique within this crate's JSON.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The item's name. Items such as impls and `use` imports have none.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The item's doc comment as written, without any link rewriting or
+    /// other processing.
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// The single-line signature of this item, without its visibility or
+    /// body, e.g. `fn encode(bytes: &[u8]) -> String`, `struct Ref<'a, T>`,
+    /// `trait Shape: Clone`, `const SIZE: usize` or `impl Clone for Shape`.
+    /// This is the form used by [`RustDoc::summary`], the embedding docs, the
+    /// public API hash and [`RustDoc::implementors`]. Returns `None` for kinds
+    /// without one, such as imports and primitives.
+    pub fn signature(&self) -> Option<String> {
+        let inner = self.inner.as_ref()?;
+        if let Some(impl_) = &inner.impl_ {
+            return Some(impl_.format_header());
+        }
+        let name = self.name.as_ref()?;
+        if let Some(function) = &inner.function {
+            return Some(function.format(name, false, None));
+        }
+        if let Some(struct_details) = &inner.struct_ {
+            let params = struct_details.generics.format_params();
+            let where_clause = struct_details.generics.format_where_clause();
+            return Some(format!("struct {name}{params}{where_clause}"));
+        }
+        if let Some(enum_details) = &inner.enum_ {
+            let params = enum_details.generics.format_params();
+            let where_clause = enum_details.generics.format_where_clause();
+            return Some(format!("enum {name}{params}{where_clause}"));
+        }
+        if let Some(trait_details) = &inner.trait_ {
+            return Some(trait_details.format_header(name));
+        }
+        if let Some(type_alias) = &inner.type_alias {
+            let params = type_alias.generics.format_params();
+            let type_ = &type_alias.type_;
+            let where_clause = type_alias.generics.format_where_clause();
+            return Some(format!(
+                "type {name}{params}{where_clause} = {type_}"
+            ));
+        }
+        if let Some(trait_alias) = &inner.trait_alias {
+            let params = trait_alias.generics.format_params();
+            let bounds = format_bounds(&trait_alias.params);
+            let where_clause = trait_alias.generics.format_where_clause();
+            return Some(format!(
+                "trait {name}{params} = {bounds}{where_clause}"
+            ));
+        }
+        if let Some(constant) = &inner.constant {
+            let type_ = &constant.type_;
+            return Some(format!("const {name}: {type_}"));
+        }
+        if let Some(static_) = &inner.static_ {
+            let mut_ = if static_.mutable { "mut " } else { "" };
+            let type_ = &static_.type_;
+            return Some(format!("static {mut_}{name}: {type_}"));
+        }
+        if let Some(assoc_const) = &inner.assoc_const {
+            let type_ = &assoc_const.type_;
+            return Some(format!("const {name}: {type_}"));
+        }
+        if let Some(assoc_type) = &inner.assoc_type {
+            let params = assoc_type.generics.format_params();
+            return Some(format!("type {name}{params}"));
+        }
+        if inner.module.is_some() {
+            return Some(format!("mod {name}"));
+        }
+        None
+    }
+
+    /// The markdown entry for this item: its heading, definition, docs, and
+    /// impls. Returns `None` for items which aren't printed, such as those
+    /// without docs, or re-exports unless [`PrintOptions::inline_reexports`]
+    /// is set.
+    pub fn to_markdown(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let _max_type_depth = MaxTypeDepth::set(opts.max_type_depth);
+        let _truncation_marker =
+            TruncationMarker::set(opts.truncation_marker.clone());
+        // Its docs are already printed with its struct
+        if doc.is_inlined_field(&self.id, opts) {
+            return None;
+        }
+        if let Some(name) = &self.name {
+            return self.format_named(name, None, doc, opts);
+        }
+        let import = self.inner.as_ref()?.import.as_ref()?;
+        if !opts.inline_reexports {
+            return None;
+        }
+        import.format_inlined(doc, opts)
+    }
+
+    /// Like [`RustDocItem::to_markdown`], but with [`PrintOptions::strict`],
+    /// fails if this item or one of its associated items is of a kind which
+    /// isn't supported, rather than skipping it.
+    pub fn try_to_markdown(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Result<Option<String>, RenderError> {
+        if opts.strict {
+            self.check_supported(doc)?;
+        }
+        Ok(self.to_markdown(doc, opts))
+    }
+
+    /// Fails if this item or one of its trait's or impl's items is of an
+    /// unsupported kind. See [`RenderError`].
+    fn check_supported(&self, doc: &RustDoc) -> Result<(), RenderError> {
+        let Some(inner) = &self.inner else {
+            return Ok(());
+        };
+        if let Some(kind) = inner.unsupported.keys().next() {
+            return Err(RenderError::UnsupportedKind {
+                id: self.id.clone(),
+                kind: kind.clone(),
+            });
+        }
+        let assoc_item_ids = match (&inner.trait_, &inner.impl_) {
+            (Some(trait_details), _) => trait_details.items.as_slice(),
+            (None, Some(impl_)) => impl_.items.as_slice(),
+            (None, None) => &[],
+        };
+        assoc_item_ids
+            .iter()
+            .filter_map(|id| doc.index.get(id))
+            .try_for_each(|item| item.check_supported(doc))
+    }
+
+    /// Formats this item under the heading `name`. `reexported_from` is the
+    /// original path of a re-exported item, which is noted in its definition.
+    fn format_named(
+        &self,
+        name: &str,
+        reexported_from: Option<&str>,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let docs = self.docs.as_ref()?;
+
+        let renderer = opts.format.renderer();
+        let mut out = renderer.separator().to_owned();
+        // Re-exports are printed alongside their target, which has the anchor
+        if reexported_from.is_none() {
+            if let Some(anchor) = doc.anchor(&self.id) {
+                out.push_str(&renderer.anchor(&anchor));
+            }
+        }
+        let qualified_name = match reexported_from {
+            None if opts.qualified_names => doc.relative_path(&self.id),
+            _ => None,
+        };
+        let qualified_name = match doc.assoc_item_owner(&self.id) {
+            Some(owner) if opts.qualified_assoc_items =>
+                Some(format!("{owner}::{name}")),
+            _ => qualified_name,
+        };
+        let heading = self
+            .format_heading(qualified_name.as_deref().unwrap_or(name), opts);
+        out.push_str(&format!("{heading}\n\n"));
+
+        let note = |text: &str| renderer.paragraph(&renderer.text(text));
+        if let Some(deprecation) = &self.deprecation {
+            out.push_str(&note(&deprecation.to_string()));
+        }
+
+        let trait_details = self.inner.as_ref().and_then(|i| i.trait_.as_ref());
+        if let Some(trait_details) = trait_details {
+            if opts.object_safety_notes && !trait_details.is_object_safe(doc) {
+                out.push_str(&note(
+                    "Not object-safe (cannot be used as dyn Trait)",
+                ));
+            }
+            if opts.sealed_notes && trait_details.is_sealed(doc) {
+                out.push_str(&note(
+                    "Sealed (cannot be implemented outside this crate)",
+                ));
+            }
+        }
+
+        if let Some(code_block) =
+            self.format_code_block(name, reexported_from, doc, opts)
+        {
+            out.push_str(&format!("{code_block}\n"));
+        }
+
+        let docs = self.format_docs(docs, doc, opts);
+        out.push_str(&renderer.paragraph(&docs));
+
+        if opts.non_exhaustive_notes && self.is_non_exhaustive_struct(doc) {
+            out.push_str(&note(
+                "Note: this struct is `#[non_exhaustive]`, so it can't be \
+                 constructed with a struct literal outside its crate, even \
+                 though it has public fields.",
+            ));
+        }
+
+        let inlined_enums = self.format_inlined_enums(doc, opts);
+        if !inlined_enums.is_empty() {
+            let code_block = format_code_fence(&inlined_enums, opts);
+            let labeled =
+                renderer.labeled_block("Referenced enums", &code_block);
+            out.push_str(&format!("{labeled}\n"));
+        }
+
+        if let Some(impl_ids) = self.impl_ids(opts) {
+            let inherent_impls = format_inherent_impls(impl_ids, doc, opts);
+            out.push_str(&inherent_impls);
+
+            let trait_impls = format_trait_impls(impl_ids, doc, opts);
+            if !trait_impls.is_empty() {
+                let code_block = format_code_fence(&trait_impls, opts);
+                let labeled = renderer
+                    .labeled_block("Trait implementations", &code_block);
+                out.push_str(&format!("{labeled}\n"));
+            }
+
+            if opts.auto_trait_impls {
+                if let Some(auto_traits) =
+                    format_auto_trait_impls(impl_ids, doc)
+                {
+                    out.push_str(&note(&auto_traits));
+                }
+            }
+        }
+        Some(out)
+    }
+
+    /// Formats this associated const, type, or fn as it's declared in a trait
+    /// or impl, e.g. `const ID: u32 = 7;` or `type Item<T> = Vec<T>;`.
+    /// With [`PrintOptions::ufcs_methods`], fns are qualified by `qualifier`,
+    /// e.g. `Tally`, and their receivers are given `self_type`, e.g.
+    /// `Tally<u32>`, in place of `Self`.
+    fn format_assoc_item(
+        &self,
+        opts: &PrintOptions,
+        qualifier: &str,
+        self_type: &str,
+    ) -> Option<String> {
+        let (Some(name), Some(inner)) = (&self.name, &self.inner) else {
+            return None;
+        };
+        if let Some(assoc_const) = &inner.assoc_const {
+            let type_ = &assoc_const.type_;
+            return match &assoc_const.default {
+                Some(value) =>
+                    Some(format!("const {name}: {type_} = {value};")),
+                None => Some(format!("const {name}: {type_};")),
+            };
+        }
+        if let Some(assoc_type) = &inner.assoc_type {
+            let params = assoc_type.generics.format_params();
+            let bounds = match assoc_type.bounds.as_slice() {
+                [] => String::new(),
+                bounds => format!(": {}", format_bounds(bounds)),
+            };
+            let default = match &assoc_type.default {
+                Some(type_) => format!(" = {type_}"),
+                None => String::new(),
+            };
+            let where_clause = assoc_type.generics.format_where_clause();
+            return Some(format!(
+                "type {name}{params}{bounds}{default}{where_clause};"
+            ));
+        }
+        if let Some(function) = &inner.function {
+            // Trait and trait impl items have "default" visibility
+            let vis = self.visibility_prefix();
+            let signature = if opts.ufcs_methods {
+                let name = format!("{qualifier}::{name}");
+                function.format(
+                    &name,
+                    opts.positional_param_names,
+                    Some(self_type),
+                )
+            } else {
+                function.format(name, opts.positional_param_names, None)
+            };
+            let note = if opts.object_safety_notes
+                && function.generics.requires_self_sized()
+            {
+                " // requires Self: Sized, so can't be called on dyn Trait"
+            } else {
+                ""
+            };
+            return Some(format!("{vis}{signature};{note}"));
+        }
+        None
+    }
+
+    /// If this is a fn, the definitions of the small local enums its
+    /// signature mentions. See [`PrintOptions::inline_enums_max_variants`].
+    fn format_inlined_enums(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> String {
+        let Some(max_variants) = opts.inline_enums_max_variants else {
+            return String::new();
+        };
+        let Some(function) = self
+            .inner
+            .as_ref()
+            .and_then(|inner| inner.function.as_ref())
+        else {
+            return String::new();
+        };
+
+        let mut enum_ids = Vec::new();
+        function.decl.visit_paths(&mut |path| {
+            if let Some(id) = &path.id {
+                if !enum_ids.contains(id) {
+                    enum_ids.push(id.clone());
+                }
+            }
+        });
+        enum_ids
+            .iter()
+            .filter(|id| id.starts_with("0:"))
+            .filter_map(|id| doc.index.get(id))
+            .filter(|item| {
+                let enum_ = item.inner.as_ref().and_then(|i| i.enum_.as_ref());
+                enum_.is_some_and(|enum_| enum_.variants.len() <= max_variants)
+            })
+            .filter_map(|item| {
+                item.format_definition(item.name.as_ref()?, doc, opts)
+            })
+            .collect()
+    }
+
+    /// Rewrites the intra-doc links in `docs` to in-document anchors, e.g.
+    /// ``[`push`](#vecpush)``, if their target is rendered in this document.
+    /// Links to targets which aren't rendered are reduced to their text.
+    /// Code blocks are left untouched, as is everything with
+    /// [`PrintOptions::raw_docs`].
+    fn format_docs(
+        &self,
+        docs: &str,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> String {
+        let renderer = opts.format.renderer();
+        if opts.raw_docs {
+            return renderer.text(docs);
+        }
+        let transformed = opts
+            .doc_transform
+            .as_ref()
+            .map(|DocTransform(transform)| transform(docs));
+        let docs = transformed.as_deref().unwrap_or(docs);
+        let resolve = |dest: &str| {
+            let id = self.links.get(dest)?;
+            if !doc.is_rendered(id, opts) {
+                return Some(None);
+            }
+            Some(doc.anchor(id))
+        };
+
+        let mut in_code_block = false;
+        docs.lines()
+            .map(|line| {
+                if line.trim_start().starts_with("```") {
+                    in_code_block = !in_code_block;
+                }
+                if in_code_block {
+                    return renderer.text(line);
+                }
+                let line = rewrite_doc_links(line, resolve, renderer);
+                match markdown_heading(&line) {
+                    Some((level, text))
+                        if opts.format == OutputFormat::Markdown =>
+                    {
+                        let level = (level + opts.doc_heading_offset).min(6);
+                        let hashes = "#".repeat(level);
+                        format!("{hashes} {text}")
+                    }
+                    _ => line,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The heading this item is printed under, e.g. `` `encode`: ``.
+    fn format_heading(&self, name: &str, opts: &PrintOptions) -> String {
+        let renderer = opts.format.renderer();
+        let primitive = self.inner.as_ref().and_then(|i| i.primitive.as_ref());
+        match primitive {
+            Some(primitive) => renderer.primitive_heading(&primitive.name),
+            None => renderer.item_heading(name),
+        }
+    }
+
+    /// The ids of the impls to list under this type, if it's a struct, enum,
+    /// or primitive. A primitive's impls are only listed with
+    /// [`PrintOptions::expand_impl_items`], as `std`'s have hundreds each.
+    fn impl_ids(&self, opts: &PrintOptions) -> Option<&[String]> {
+        let inner = self.inner.as_ref()?;
+        if let Some(primitive) = &inner.primitive {
+            return opts.expand_impl_items.then_some(&primitive.impls);
+        }
+        if let Some(enum_details) = &inner.enum_ {
+            return Some(&enum_details.impls);
+        }
+        if let Some(struct_details) = &inner.struct_ {
+            return Some(&struct_details.impls);
+        }
+        None
+    }
+
+    /// Formats this struct field as `pub name: Type`, or as `pub Type` for
+    /// the unnamed fields of a tuple struct.
+    fn format_field(&self, named: bool) -> Option<String> {
+        let type_ = self.inner.as_ref()?.struct_field.as_ref()?;
+        let vis = self.visibility_prefix();
+        if !named {
+            return Some(format!("{vis}{type_}"));
+        }
+        let name = self.name.as_ref()?;
+        Some(format!("{vis}{name}: {type_}"))
+    }
+
+    /// `"pub "` if this item is public, otherwise an empty string.
+    fn visibility_prefix(&self) -> &'static str {
+        match self.visibility.as_deref() {
+            Some("public") => "pub ",
+            _ => "",
+        }
+    }
+
+    /// Formats the definition of this item as a fenced code block.
+    fn format_code_block(
+        &self,
+        name: &str,
+        reexported_from: Option<&str>,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let definition = self.format_definition(name, doc, opts)?;
+        let attribution = reexported_from
+            .map(|source| format!("/* re-exported from {source} */\n"))
+            .unwrap_or_default();
+        let returns = opts
+            .tuple_return_notes
+            .then(|| self.format_tuple_return_notes())
+            .flatten()
+            .unwrap_or_default();
+        let fallibility = opts
+            .fallibility_notes
+            .then(|| self.format_fallibility_note())
+            .flatten()
+            .unwrap_or_default();
+        let code = format!("{attribution}{definition}{returns}{fallibility}");
+        Some(format_code_fence(&code, opts))
+    }
+
+    /// If this fn returns a tuple, the `# Returns` section of its docs as
+    /// line comments. See [`PrintOptions::tuple_return_notes`].
+    fn format_tuple_return_notes(&self) -> Option<String> {
+        let function = self.inner.as_ref()?.function.as_ref()?;
+        let Some(Type::Tuple { .. }) = &function.decl.output else {
+            return None;
+        };
+        let section = doc_section(self.docs.as_deref()?, "Returns")?;
+        let notes = section
+            .lines()
+            .map(|line| match line {
+                "" => "//\n".to_owned(),
+                line => format!("// {line}\n"),
+            })
+            .collect::<String>();
+        Some(format!("// Returns:\n{notes}"))
+    }
+
+    /// If this fn returns an `Option` or `Result`, a line comment saying when
+    /// it returns `None` or `Err`. See [`PrintOptions::fallibility_notes`].
+    fn format_fallibility_note(&self) -> Option<String> {
+        let function = self.inner.as_ref()?.function.as_ref()?;
+        let Some(Type::ResolvedPath { resolved_path }) = &function.decl.output
+        else {
+            return None;
+        };
+        // Also matches paths like `std::io::Result`
+        let (note, section_title) =
+            match resolved_path.name.rsplit("::").next()? {
+                "Option" => ("Returns None when absent", "Returns"),
+                "Result" => ("Returns Err on failure", "Errors"),
+                _ => return None,
+            };
+        let section = self
+            .docs
+            .as_deref()
+            .and_then(|docs| doc_section(docs, section_title))
+            .map(docs_summary)
+            .filter(|summary| !summary.is_empty());
+        match section {
+            Some(summary) => Some(format!("// {note}: {summary}\n")),
+            None => Some(format!("// {note}.\n")),
+        }
+    }
+
+    /// Formats the Rust definition of this item, e.g. `pub fn foo();` or
+    /// `pub enum Foo { .. }`, for printing inside a code block.
+    fn format_definition(
+        &self,
+        name: &str,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let inner = self.inner.as_ref()?;
+        if let Some(f) = &inner.function {
+            if f.is_foreign() {
+                let signature =
+                    f.format(name, opts.positional_param_names, None);
+                let abi = &f.header.abi;
+                return Some(format!(
+                    "extern \"{abi}\" {{\n    pub {signature};\n}}\n"
+                ));
+            }
+            let signature =
+                f.format_unbounded(name, opts.positional_param_names, None);
+            let where_clause =
+                f.generics.format_definition_where_clause(opts, ";");
+            return Some(format!("pub {signature}{where_clause}\n"));
+        }
+        if let Some(static_) = &inner.static_ {
+            let mut_ = if static_.mutable { "mut " } else { "" };
+            let type_ = &static_.type_;
+            let declaration = format!("pub static {mut_}{name}: {type_}");
+            // Statics declared in `extern` blocks have no initializer. rustdoc
+            // doesn't record the block's ABI, but `extern` defaults to "C".
+            if static_.expr.is_empty() {
+                return Some(format!("extern {{\n    {declaration};\n}}\n"));
+            }
+            let expr = format_const_value(&static_.expr, opts);
+            return Some(format!("{declaration} = {expr};\n"));
+        }
+        if let Some(constant) = &inner.constant {
+            let type_ = &constant.type_;
+            let expr = format_const_value(&constant.const_.expr, opts);
+            return Some(format!("pub const {name}: {type_} = {expr};\n"));
+        }
+        if let Some(enum_details) = &inner.enum_ {
+            // The repr determines the discriminants' type, so it's always
+            // printed
+            let mut out = self
+                .attrs
+                .iter()
+                .filter(|attr| attr.starts_with("#[repr("))
+                .map(|attr| format!("{attr}\n"))
+                .collect::<String>();
+            let params = enum_details.generics.format_params();
+            let where_clause = enum_details
+                .generics
+                .format_definition_where_clause(opts, " {");
+            out.push_str(&format!("pub enum {name}{params}{where_clause}\n"));
+            for variant_id in &enum_details.variants {
+                if let Some(variant) = doc.index.get(variant_id) {
+                    if let Some(docs) = &variant.docs {
+                        out.push_str(&format_doc_comment(docs, "    "));
+                    }
+                    if opts.variant_attrs {
+                        for attr in variant.allowed_attrs() {
+                            out.push_str(&format!("    {attr}\n"));
+                        }
+                    }
+                    let details =
+                        variant.inner.as_ref().and_then(|i| i.variant.as_ref());
+                    let fields = details
+                        .map(|details| details.format_fields(doc))
+                        .unwrap_or_default();
+                    let discriminant = details
+                        .and_then(|details| details.discriminant.as_ref())
+                        .map(|discriminant| format!(" = {}", discriminant.expr))
+                        .unwrap_or_default();
+                    if let Some(name) = &variant.name {
+                        out.push_str(&format!(
+                            "    {name}{fields}{discriminant},\n"
+                        ));
+                    }
+                }
+            }
+            out.push_str("}\n");
+            return Some(out);
+        }
+        if let Some(struct_details) = &inner.struct_ {
+            return Some(struct_details.format(name, doc, opts));
+        }
+        if let Some(trait_details) = &inner.trait_ {
+            return Some(trait_details.format(name, doc, opts));
+        }
+        if let Some(type_alias) = &inner.type_alias {
+            let params = type_alias.generics.format_params();
+            let type_ = &type_alias.type_;
+            let where_clause = type_alias.generics.format_where_clause();
+            // Unlike an associated type's, a type alias' where clause must
+            // precede its type
+            return Some(format!(
+                "pub type {name}{params}{where_clause} = {type_};\n"
+            ));
+        }
+        if let Some(trait_alias) = &inner.trait_alias {
+            let params = trait_alias.generics.format_params();
+            let bounds = format_bounds(&trait_alias.params);
+            let where_clause = trait_alias.generics.format_where_clause();
+            return Some(format!(
+                "pub trait {name}{params} = {bounds}{where_clause};\n"
+            ));
+        }
+        None
+    }
+}
+
+impl ItemInner {
+    /// Removes the references to the items with the given ids. See
+    /// [`RustDoc::filter_map_items`].
+    fn remove_references(&mut self, removed: &HashSet<String>) {
+        let kept = |id: &String| !removed.contains(id);
+        if let Some(module) = &mut self.module {
+            module.items.retain(kept);
+        }
+        if let Some(import) = &mut self.import {
+            import.id = import.id.take().filter(kept);
+        }
+        if let Some(enum_details) = &mut self.enum_ {
+            enum_details.variants.retain(kept);
+            enum_details.impls.retain(kept);
+        }
+        if let Some(variant) = &mut self.variant {
+            match &mut variant.kind {
+                VariantKind::Plain => {}
+                VariantKind::Tuple(fields) =>
+                    remove_tuple_fields(fields, removed),
+                VariantKind::Struct {
+                    fields,
+                    fields_stripped,
+                } => remove_fields(fields, fields_stripped, removed),
+            }
+        }
+        if let Some(struct_details) = &mut self.struct_ {
+            match &mut struct_details.kind {
+                StructKind::Unit => {}
+                StructKind::Tuple(fields) =>
+                    remove_tuple_fields(fields, removed),
+                StructKind::Plain {
+                    fields,
+                    fields_stripped,
+                } => remove_fields(fields, fields_stripped, removed),
+            }
+            struct_details.impls.retain(kept);
+        }
+        if let Some(primitive) = &mut self.primitive {
+            primitive.impls.retain(kept);
+        }
+        if let Some(trait_details) = &mut self.trait_ {
+            trait_details.items.retain(kept);
+            trait_details.implementations.retain(kept);
+        }
+        if let Some(impl_) = &mut self.impl_ {
+            impl_.items.retain(kept);
+        }
+    }
+}
+
+/// Removes the `removed` fields of a struct or struct variant, marking its
+/// fields as stripped if any were.
+fn remove_fields(
+    fields: &mut Vec<String>,
+    fields_stripped: &mut bool,
+    removed: &HashSet<String>,
+) {
+    let len = fields.len();
+    fields.retain(|id| !removed.contains(id));
+    *fields_stripped |= fields.len() < len;
+}
+
+/// Replaces the `removed` fields of a tuple struct or variant with `None`, as
+/// if they were hidden, so the others keep their positions.
+fn remove_tuple_fields(
+    fields: &mut [Option<String>],
+    removed: &HashSet<String>,
+) {
+    for field in fields {
+        if field.as_ref().is_some_and(|id| removed.contains(id)) {
+            *field = None;
+        }
+    }
+}
+
+impl TraitDetails {
+    /// Formats the trait's declaration without its visibility or items, e.g.
+    /// `unsafe trait Foo<T>: Clone where T: Send`.
+    fn format_header(&self, name: &str) -> String {
+        let declaration = self.format_declaration(name);
+        let where_clause = self.generics.format_where_clause();
+        format!("{declaration}{where_clause}")
+    }
+
+    /// [`Self::format_header`] without the where clause, e.g.
+    /// `unsafe trait Foo<T>: Clone`.
+    fn format_declaration(&self, name: &str) -> String {
+        let unsafe_ = if self.is_unsafe { "unsafe " } else { "" };
+        let auto = if self.is_auto { "auto " } else { "" };
+        let params = self.generics.format_params();
+        let supertraits = match self.bounds.as_slice() {
+            [] => String::new(),
+            bounds => format!(": {}", format_bounds(bounds)),
+        };
+        format!("{unsafe_}{auto}trait {name}{params}{supertraits}")
+    }
+
+    /// Formats the trait definition with the declarations of its items, e.g.
+    /// `pub trait Foo: Clone { fn foo(&self); }`.
+    fn format(&self, name: &str, doc: &RustDoc, opts: &PrintOptions) -> String {
+        let declaration = self.format_declaration(name);
+        let where_clause =
+            self.generics.format_definition_where_clause(opts, " {");
+        let mut out = format!("pub {declaration}{where_clause}\n");
+        for item in self.items.iter().filter_map(|id| doc.index.get(id)) {
+            if let Some(assoc_item) = item.format_assoc_item(opts, name, "Self")
+            {
+                out.push_str(&format!("    {assoc_item}\n"));
+            }
+        }
+        if opts.inherited_methods {
+            out.push_str(&self.format_inherited_methods(doc, opts));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Formats the methods of this trait's supertraits, and theirs in turn,
+    /// as comments grouped by supertrait, e.g. `// From `Named`:`. Only this
+    /// crate's supertraits can be looked up. See
+    /// [`PrintOptions::inherited_methods`].
+    fn format_inherited_methods(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> String {
+        let supertrait_ids = |trait_: &TraitDetails| {
+            trait_
+                .bounds
+                .iter()
+                .filter_map(|bound| match bound {
+                    GenericBound::TraitBound { trait_bound } =>
+                        trait_bound.trait_.id.clone(),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut queue = VecDeque::from(supertrait_ids(self));
+        // Guards against cycles, which rustc rejects but the JSON may have
+        let mut visited = HashSet::new();
+        let mut out = String::new();
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+            let Some(supertrait) = doc.index.get(&id) else {
+                continue;
+            };
+            let Some(details) =
+                supertrait.inner.as_ref().and_then(|i| i.trait_.as_ref())
+            else {
+                continue;
+            };
+            if let Some(name) = &supertrait.name {
+                let methods = details
+                    .items
+                    .iter()
+                    .filter_map(|id| doc.index.get(id))
+                    .filter(|item| {
+                        item.inner
+                            .as_ref()
+                            .is_some_and(|i| i.function.is_some())
+                    })
+                    .filter_map(|item| {
+                        item.format_assoc_item(opts, name, "Self")
+                    })
+                    .collect::<Vec<_>>();
+                if !methods.is_empty() {
+                    out.push_str(&format!("    // From `{name}`:\n"));
+                    for method in methods {
+                        out.push_str(&format!("    // {method}\n"));
+                    }
+                }
+            }
+            queue.extend(supertrait_ids(details));
+        }
+        out
+    }
+
+    /// Whether this trait looks object safe, i.e. usable as `dyn Trait`.
+    ///
+    /// This is a heuristic which only looks at the trait's own declaration.
+    /// A trait isn't object safe if it requires `Self: Sized`, has associated
+    /// consts or generic associated types, or has a method which doesn't opt
+    /// out with `where Self: Sized` and either has no receiver, has type
+    /// params (including `impl Trait` args), is `async` or returns `impl
+    /// Trait`, or mentions `Self` outside of its receiver and projections
+    /// like `Self::Item`. Supertraits' items and receiver types aren't
+    /// checked, so some traits which aren't object safe are reported as if
+    /// they were.
+    fn is_object_safe(&self, doc: &RustDoc) -> bool {
+        if self.bounds.iter().any(is_sized_bound)
+            || self.generics.requires_self_sized()
+        {
+            return false;
+        }
+        let items = self.items.iter().filter_map(|id| doc.index.get(id));
+        for item in items {
+            let Some(inner) = &item.inner else { continue };
+            if inner.assoc_const.is_some() {
+                return false;
+            }
+            if let Some(assoc_type) = &inner.assoc_type {
+                if !assoc_type.generics.params.is_empty() {
+                    return false;
+                }
+            }
+            let Some(function) = &inner.function else {
+                continue;
+            };
+            if function.generics.requires_self_sized() {
+                continue;
+            }
+            let decl = &function.decl;
+            let has_type_params =
+                function.generics.params.iter().any(|param| {
+                    matches!(param.kind, GenericParamKind::Type { .. })
+                });
+            let returns_impl_trait =
+                matches!(decl.output, Some(Type::ImplTrait { .. }));
+            let mentions_self = decl
+                .inputs
+                .iter()
+                .skip(1)
+                .map(|(_name, param)| param.to_string())
+                .chain(decl.output.as_ref().map(Type::to_string))
+                .any(|type_| mentions_self(&type_));
+            if !decl.has_receiver()
+                || has_type_params
+                || function.header.async_
+                || returns_impl_trait
+                || mentions_self
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether this trait looks sealed, i.e. has a supertrait which other
+    /// crates can't name, like `private::Sealed` in
+    /// `pub trait Foo: private::Sealed` where `private` is a private module.
+    /// This is a heuristic: a sealed supertrait is one of this crate's
+    /// traits which isn't publicly reachable, including hidden traits which
+    /// rustdoc left out. Traits sealed in other ways, e.g. by a method whose
+    /// signature mentions a private type, aren't detected.
+    fn is_sealed(&self, doc: &RustDoc) -> bool {
+        self.bounds.iter().any(|bound| match bound {
+            GenericBound::TraitBound { trait_bound } =>
+                trait_bound.trait_.id.as_deref().is_some_and(|id| {
+                    id.starts_with("0:") && !doc.is_publicly_reachable(id)
+                }),
+            _ => false,
+        })
+    }
+}
+
+/// Whether `bound` is `Sized`, as opposed to `?Sized`.
+fn is_sized_bound(bound: &GenericBound) -> bool {
+    match bound {
+        GenericBound::TraitBound { trait_bound } =>
+            trait_bound.trait_.name == "Sized"
+                && matches!(trait_bound.modifier, TraitBoundModifier::None),
+        _ => false,
+    }
+}
+
+/// Whether `type_` mentions `Self` other than in a projection like
+/// `Self::Item`, e.g. `Option<Self>`.
+fn mentions_self(type_: &str) -> bool {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    type_.match_indices("Self").any(|(start, _)| {
+        let before = type_[..start].chars().next_back();
+        let after = &type_[start + "Self".len()..];
+        !before.is_some_and(is_ident_char)
+            && !after.starts_with(is_ident_char)
+            && !after.starts_with("::")
+    })
+}
+
+/// Replaces each mention of `Self` in `type_` with `self_type`, e.g.
+/// `&mut Self` with `&mut Tally<u32>`. In projections, `self_type` is
+/// qualified, e.g. `<Tally<u32>>::Item`.
+fn replace_self(type_: &str, self_type: &str) -> String {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut out = String::new();
+    let mut rest = type_;
+    while let Some(start) = rest.find("Self") {
+        let before = out.chars().chain(rest[..start].chars()).next_back();
+        let after = &rest[start + "Self".len()..];
+        out.push_str(&rest[..start]);
+        if before.is_some_and(is_ident_char) || after.starts_with(is_ident_char)
+        {
+            out.push_str("Self");
+        } else if after.starts_with("::") && self_type != "Self" {
+            out.push_str(&format!("<{self_type}>"));
+        } else {
+            out.push_str(self_type);
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+impl StructDetails {
+    /// Formats the struct definition, e.g. `pub struct Point { .. }`,
+    /// `pub struct Meters(pub f64);` or `pub struct Marker;`.
+    fn format(&self, name: &str, doc: &RustDoc, opts: &PrintOptions) -> String {
+        let params = self.generics.format_params();
+        let where_clause =
+            |end| self.generics.format_definition_where_clause(opts, end);
+        let header = format!("pub struct {name}{params}");
+        match &self.kind {
+            StructKind::Unit => format!("{header}{}\n", where_clause(";")),
+            StructKind::Tuple(field_ids) => {
+                let fields = field_ids
+                    .iter()
+                    .map(|field_id| {
+                        field_id
+                            .as_ref()
+                            .and_then(|field_id| doc.index.get(field_id))
+                            .filter(|field| field.is_visible(opts))
+                            .and_then(|field| field.format_field(false))
+                            .unwrap_or_else(|| "_".to_owned())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{header}({fields}){}\n", where_clause(";"))
+            }
+            StructKind::Plain {
+                fields,
+                fields_stripped,
+            } => {
+                let mut out = format!("{header}{}\n", where_clause(" {"));
+                let mut fields_stripped = *fields_stripped;
+                for field in fields.iter().filter_map(|id| doc.index.get(id)) {
+                    if !field.is_visible(opts) {
+                        fields_stripped = true;
+                        continue;
+                    }
+                    let Some(formatted) = field.format_field(true) else {
+                        continue;
+                    };
+                    if let Some(docs) = &field.docs {
+                        out.push_str(&format_doc_comment(docs, "    "));
+                    }
+                    out.push_str(&format!("    {formatted},\n"));
+                }
+                // As rustdoc marks them
+                if fields_stripped {
+                    out.push_str("    /* private fields */\n");
+                }
+                out.push_str("}\n");
+                out
+            }
+        }
+    }
+}
+
+/// `docs` as a JSDoc comment, with any `*/` escaped so it can't end the
+/// comment early.
+fn format_jsdoc(docs: &str) -> String {
+    let lines = docs
+        .replace("*/", "*\\/")
+        .lines()
+        .map(|line| format!(" * {line}").trim_end().to_owned() + "\n")
+        .collect::<String>();
+    format!("/**\n{lines} */\n")
+}
+
+/// A TypeScript generic params list for the type params in `generics`, e.g.
+/// `<T, U>`, or empty if there are none. Lifetimes and const params have no
+/// TypeScript equivalent, and synthetic params come from `impl Trait` args.
+fn format_ts_generic_params(generics: &Generics) -> String {
+    let names = generics
+        .params
+        .iter()
+        .filter(|param| {
+            matches!(
+                param.kind,
+                GenericParamKind::Type {
+                    synthetic: false,
+                    ..
+                }
+            )
+        })
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return String::new();
+    }
+    format!("<{}>", names.join(", "))
+}
+
+/// The TypeScript type of the JSON serde produces for `type_`, e.g.
+/// `string[]` for `Vec<String>`. See [`RustDoc::to_typescript`].
+fn format_ts_type(type_: &Type) -> String {
+    match type_ {
+        Type::Primitive { primitive } => match primitive.as_str() {
+            "bool" => "boolean".to_owned(),
+            "char" | "str" => "string".to_owned(),
+            "never" => "never".to_owned(),
+            _ => "number".to_owned(),
+        },
+        Type::Generic { generic } => generic.clone(),
+        Type::Tuple { tuple } if tuple.is_empty() => "null".to_owned(),
+        Type::Tuple { tuple } => {
+            let types = tuple.iter().map(format_ts_type).collect::<Vec<_>>();
+            format!("[{}]", types.join(", "))
+        }
+        Type::Slice { slice } => format_ts_array_type(slice),
+        Type::Array { array } => format_ts_array_type(&array.type_),
+        Type::BorrowedRef { borrowed_ref } =>
+            format_ts_type(&borrowed_ref.type_),
+        Type::ResolvedPath { resolved_path } =>
+            format_ts_path_type(resolved_path),
+        _ => "unknown".to_owned(),
+    }
+}
+
+/// An array of `type_`, with unions parenthesized, e.g. `(T | null)[]`.
+fn format_ts_array_type(type_: &Type) -> String {
+    let type_ = format_ts_type(type_);
+    if type_.contains(" | ") {
+        return format!("({type_})[]");
+    }
+    format!("{type_}[]")
+}
+
+/// A path type, mapping the common `std` types to the shape serde gives
+/// them. Other types are referred to by name, with their type args.
+fn format_ts_path_type(path: &ResolvedPath) -> String {
+    let args = match &path.args {
+        Some(GenericArgs::AngleBracketed { angle_bracketed }) =>
+            angle_bracketed
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type { type_ } => Some(type_.as_ref()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    let name = path.name.rsplit("::").next().unwrap_or(&path.name);
+    match (name, args.as_slice()) {
+        ("String" | "PathBuf", _) => "string".to_owned(),
+        ("Option", [type_]) => format!("{} | null", format_ts_type(type_)),
+        (
+            "Vec" | "VecDeque" | "HashSet" | "BTreeSet" | "BinaryHeap",
+            [type_, ..],
+        ) => format_ts_array_type(type_),
+        ("HashMap" | "BTreeMap", [key, value, ..]) => {
+            let key = format_ts_type(key);
+            let value = format_ts_type(value);
+            format!("Record<{key}, {value}>")
+        }
+        ("Result", [ok, err]) => {
+            let ok = format_ts_type(ok);
+            let err = format_ts_type(err);
+            format!("{{ Ok: {ok} }} | {{ Err: {err} }}")
+        }
+        ("Box" | "Rc" | "Arc" | "Cow", [.., type_]) => format_ts_type(type_),
+        (name, []) => name.to_owned(),
+        (name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| format_ts_type(arg))
+                .collect::<Vec<_>>();
+            format!("{name}<{}>", args.join(", "))
+        }
+    }
+}
+
+/// Formats `docs` as a `///` comment with each line indented by `indent`, as
+/// they're written above fields and variants.
+fn format_doc_comment(docs: &str, indent: &str) -> String {
+    docs.lines()
+        .map(|line| match line {
+            "" => format!("{indent}///\n"),
+            line => format!("{indent}/// {line}\n"),
+        })
+        .collect()
+}
+
+impl VariantDetails {
+    /// Formats this variant's fields as they follow its name, e.g. `(u8)` or
+    /// ` { code: u32 }`, or an empty string for a plain variant.
+    fn format_fields(&self, doc: &RustDoc) -> String {
+        match &self.kind {
+            VariantKind::Plain => String::new(),
+            VariantKind::Tuple(field_ids) => {
+                let fields = field_ids
+                    .iter()
+                    .map(|field_id| {
+                        field_id
+                            .as_ref()
+                            .and_then(|field_id| doc.index.get(field_id))
+                            .and_then(|field| field.format_field(false))
+                            .unwrap_or_else(|| "_".to_owned())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("({fields})")
+            }
+            VariantKind::Struct {
+                fields,
+                fields_stripped,
+            } => {
+                let mut fields = fields
+                    .iter()
+                    .filter_map(|id| doc.index.get(id)?.format_field(true))
+                    .collect::<Vec<_>>();
+                if *fields_stripped {
+                    fields.push("..".to_owned());
+                }
+                let fields = fields.join(", ");
+                format!(" {{ {fields} }}")
+            }
+        }
+    }
+}
+
+impl ImportDetails {
+    /// The item this import re-exports, along with its original path.
+    /// Glob imports and imports of items missing from the index are skipped.
+    fn resolve<'doc>(
+        &self,
+        doc: &'doc RustDoc,
+    ) -> Option<(&'doc RustDocItem, String)> {
+        if self.glob {
+            return None;
+        }
+        let id = self.id.as_ref()?;
+        let target = doc.index.get(id)?;
+        let source = match doc.paths.get(id) {
+            Some(summary) => summary.path.join("::"),
+            None => self.source.clone(),
+        };
+        Some((target, source))
+    }
+
+    fn format_inlined(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+    ) -> Option<String> {
+        let (target, source) = self.resolve(doc)?;
+        target.format_named(&self.name, Some(&source), doc, opts)
+    }
+}
+
+/// Displays the kind as in rustdoc's JSON, e.g. `function`.
+impl fmt::Display for RustDocItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = serde_json::to_value(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", kind.as_str().unwrap_or_default())
+    }
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(f, "No item found at `{path}`"),
+            Self::Ambiguous { path, candidates } => {
+                writeln!(f, "`{path}` is ambiguous. Candidates:")?;
+                for candidate in candidates {
+                    writeln!(f, "  {candidate}")?;
+                }
+                Ok(())
+            }
+            Self::NotRendered(path) =>
+                write!(f, "`{path}` has no docs to render"),
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedKind { id, kind } =>
+                write!(f, "Item {id} is of unsupported kind `{kind}`"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl ApiSummary {
+    /// Get the item with the given fully-qualified path.
+    pub fn get(&self, path: &str) -> Option<&ApiItem> {
+        self.items.iter().find(|item| item.path == path)
+    }
+}
+
+impl Index<&str> for ApiSummary {
+    type Output = ApiItem;
+
+    fn index(&self, path: &str) -> &ApiItem {
+        self.get(path)
+            .unwrap_or_else(|| panic!("No item with path `{path}`"))
+    }
+}
+
+impl IntoIterator for ApiSummary {
+    type Item = ApiItem;
+    type IntoIter = std::vec::IntoIter<ApiItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ApiSummary {
+    type Item = &'a ApiItem;
+    type IntoIter = std::slice::Iter<'a, ApiItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl From<ApiItem> for EmbeddingDoc {
+    fn from(item: ApiItem) -> Self {
+        let text = [item.signature, item.docs_summary]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let module = match item.path.rsplit_once("::") {
+            Some((module, _name)) => module.to_owned(),
+            None => String::new(),
+        };
+        Self {
+            metadata: EmbeddingMetadata {
+                kind: item.kind,
+                module,
+                deprecated: item.deprecated,
+            },
+            id: item.path,
+            text,
+        }
+    }
+}
+
+impl ImplDetails {
+    /// Whether rustdoc inferred this impl, e.g. an auto trait impl like
+    /// `impl Send for Foo`.
+    fn is_synthetic(&self) -> bool {
+        self.synthetic
+    }
+
+    /// Whether this is a copy of a blanket impl, e.g.
+    /// `impl<T: Display> ToString for T`. See [`ImplDetails::blanket_impl`].
+    fn is_blanket(&self) -> bool {
+        self.blanket_impl.is_some()
+    }
+
+    /// The name of the type this impl is for, without generic args, e.g.
+    /// `Wrapper` for `impl<T> Wrapper<T>`. Types without a name, like
+    /// `&str`, are written qualified, e.g. `<&str>`.
+    fn self_type_name(&self) -> String {
+        match &self.for_ {
+            Type::ResolvedPath { resolved_path } => resolved_path
+                .name
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+            Type::Primitive { primitive } =>
+                format_primitive(primitive).to_owned(),
+            for_ => format!("<{for_}>"),
+        }
+    }
+
+    /// Formats this impl as `impl<T> Trait for Type<T> { ... }`, listing the
+    /// impl's associated items if `expand` is set. Blanket impls are
+    /// formatted generically, e.g. `impl<T> From<T> for T`.
+    fn format(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+        expand: bool,
+    ) -> String {
+        let header = self.format_header();
+        if self.items.is_empty() {
+            return format!("{header} {{}}\n");
+        }
+        if !expand {
+            let marker = &opts.truncation_marker;
+            return format!("{header} {{ {marker} }}\n");
+        }
+
+        self.format_items(doc, opts, |_| true).unwrap_or_default()
+    }
+
+    /// Formats `impl<T> Trait for Type<T>`, with any where clause.
+    fn format_header(&self) -> String {
+        let for_ = self.blanket_impl.as_ref().unwrap_or(&self.for_);
+        let params = self.generics.format_params();
+        let header = match &self.trait_ {
+            Some(trait_) => {
+                let trait_name = &trait_.name;
+                let trait_args = format_generic_args(trait_.args.as_ref());
+                format!("impl{params} {trait_name}{trait_args} for {for_}")
+            }
+            None => format!("impl{params} {for_}"),
+        };
+        let where_clause = self.generics.format_where_clause();
+        format!("{header}{where_clause}")
+    }
+
+    /// Formats this impl with only the associated items matching `filter`,
+    /// or `None` if none match.
+    fn format_items(
+        &self,
+        doc: &RustDoc,
+        opts: &PrintOptions,
+        filter: impl Fn(&RustDocItem) -> bool,
+    ) -> Option<String> {
+        let self_type = self.for_.to_string();
+        let qualifier = match &self.trait_ {
+            Some(trait_) => {
+                let trait_name = &trait_.name;
+                let trait_args = format_generic_args(trait_.args.as_ref());
+                format!("<{self_type} as {trait_name}{trait_args}>")
+            }
+            None => self.self_type_name(),
+        };
+        let assoc_items = self
+            .items
+            .iter()
+            .filter_map(|id| doc.index.get(id))
+            .filter(|item| item.is_visible(opts) && filter(item))
+            .filter_map(|item| {
+                item.format_assoc_item(opts, &qualifier, &self_type)
+            })
+            .collect::<Vec<_>>();
+        if assoc_items.is_empty() {
+            return None;
+        }
+
+        let header = self.format_header();
+        let mut out = format!("{header} {{\n");
+        for assoc_item in assoc_items {
+            out.push_str(&format!("    {assoc_item}\n"));
+        }
+        out.push_str("}\n");
+        Some(out)
+    }
+}
+
+/// How an item in an inherent impl is grouped when it's printed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ImplItemGroup {
+    /// Associated consts and types
+    AssocItems,
+    /// Fns without a `self` receiver, e.g. `Foo::new`
+    Functions,
+    /// Fns with a `self` receiver
+    Methods,
+}
+
+impl ImplItemGroup {
+    const ALL: [Self; 3] = [Self::AssocItems, Self::Functions, Self::Methods];
+
+    fn of(item: &RustDocItem) -> Self {
+        let function = item.inner.as_ref().and_then(|i| i.function.as_ref());
+        match function {
+            Some(function) if function.decl.has_receiver() => Self::Methods,
+            Some(_) => Self::Functions,
+            None => Self::AssocItems,
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Self::AssocItems => "Associated items",
+            Self::Functions => "Functions",
+            Self::Methods => "Methods",
+        }
+    }
+}
+
+impl FunctionDetails {
+    /// Whether this fn is declared in an `extern` block. rustdoc doesn't flag
+    /// these directly, but they're the only bodiless fns with a non-Rust ABI.
+    fn is_foreign(&self) -> bool {
+        !self.has_body && self.header.abi != Abi::Rust
+    }
+
+    /// Formats the signature including qualifiers, e.g.
+    /// `const unsafe fn name(params) -> Output`. Foreign fns are formatted
+    /// as they're written inside their `extern` block, without qualifiers.
+    /// See [`FunctionDecl::format`] for `positional_names` and `self_type`.
+    fn format(
+        &self,
+        name: &str,
+        positional_names: bool,
+        self_type: Option<&str>,
+    ) -> String {
+        let signature =
+            self.format_unbounded(name, positional_names, self_type);
+        let where_clause = self.generics.format_where_clause();
+        format!("{signature}{where_clause}")
+    }
+
+    /// [`Self::format`] without the where clause.
+    fn format_unbounded(
+        &self,
+        name: &str,
+        positional_names: bool,
+        self_type: Option<&str>,
+    ) -> String {
+        // Only the fn's own generics; those of an enclosing impl are printed
+        // on the `impl` line.
+        let params = self.generics.format_params();
+        let name = format!("{name}{params}");
+        let signature = self.decl.format(&name, positional_names, self_type);
+        if self.is_foreign() {
+            return signature;
+        }
+
+        let header = &self.header;
+        let mut qualifiers = String::new();
+        if header.const_ {
+            qualifiers.push_str("const ");
+        }
+        if header.async_ {
+            qualifiers.push_str("async ");
+        }
+        if header.unsafe_ {
+            qualifiers.push_str("unsafe ");
+        }
+        if header.abi != Abi::Rust {
+            let abi = &header.abi;
+            qualifiers.push_str(&format!("extern \"{abi}\" "));
+        }
+        format!("{qualifiers}{signature}")
+    }
+}
+
+impl Generics {
+    /// Formats the declared generic params, e.g. `<'a, T: Clone>`, or an
+    /// empty string if there are none. Synthetic `impl Trait` params are
+    /// omitted, as they're printed in the argument position instead.
+    fn format_params(&self) -> String {
+        let mut params = self
+            .params
+            .iter()
+            .filter(|param| {
+                !matches!(
+                    param.kind,
+                    GenericParamKind::Type {
+                        synthetic: true,
+                        ..
+                    }
+                )
+            })
+            .collect::<Vec<_>>();
+        // Rust requires lifetimes to come first. The sort is stable, so type
+        // and const params keep their declaration order.
+        params.sort_by_key(|param| {
+            !matches!(param.kind, GenericParamKind::Lifetime { .. })
+        });
+        let params = params
+            .into_iter()
+            .map(GenericParam::to_string)
+            .collect::<Vec<_>>();
+        if params.is_empty() {
+            return String::new();
+        }
+        let params = params.join(", ");
+        format!("<{params}>")
+    }
+
+    /// Whether there's a `where Self: Sized` predicate.
+    fn requires_self_sized(&self) -> bool {
+        self.where_predicates
+            .iter()
+            .any(|predicate| match predicate {
+                WherePredicate::Bound { bound_predicate } =>
+                    matches!(
+                        &bound_predicate.type_,
+                        Type::Generic { generic } if generic == "Self"
+                    ) && bound_predicate.bounds.iter().any(is_sized_bound),
+                _ => false,
+            })
+    }
+
+    /// Formats the where clause of a definition followed by `end`, which
+    /// is `;` or ` {`. With at least
+    /// [`PrintOptions::multiline_where_predicates`] predicates, each is put
+    /// on its own line as rustfmt does, e.g. `\nwhere\n    T: Clone,\n{`.
+    /// Otherwise it's inline, as from [`Self::format_where_clause`].
+    fn format_definition_where_clause(
+        &self,
+        opts: &PrintOptions,
+        end: &str,
+    ) -> String {
+        let is_multiline = opts
+            .multiline_where_predicates
+            .is_some_and(|min| self.where_predicates.len() >= min.max(1));
+        if !is_multiline {
+            let where_clause = self.format_where_clause();
+            return format!("{where_clause}{end}");
+        }
+        let predicates = self
+            .where_predicates
+            .iter()
+            .map(|predicate| format!("    {predicate}"))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        match end.trim() {
+            // rustfmt drops the trailing comma before a semicolon
+            ";" => format!("\nwhere\n{predicates};"),
+            end => format!("\nwhere\n{predicates},\n{end}"),
+        }
+    }
+
+    /// Formats the where clause with a leading space, e.g.
+    /// ` where T: ?Sized`, or an empty string if there are no predicates.
+    fn format_where_clause(&self) -> String {
+        if self.where_predicates.is_empty() {
+            return String::new();
+        }
+        let predicates = self
+            .where_predicates
+            .iter()
+            .map(WherePredicate::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" where {predicates}")
+    }
+}
+
+impl fmt::Display for WherePredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bound { bound_predicate } => {
+                let binder = format_binder(&bound_predicate.generic_params);
+                let type_ = &bound_predicate.type_;
+                let bounds = format_bounds(&bound_predicate.bounds);
+                write!(f, "{binder}{type_}: {bounds}")
+            }
+            Self::Region { region_predicate } => {
+                let lifetime = &region_predicate.lifetime;
+                let bounds = format_bounds(&region_predicate.bounds);
+                write!(f, "{lifetime}: {bounds}")
+            }
+            Self::Eq { eq_predicate } => {
+                let lhs = &eq_predicate.lhs;
+                let rhs = &eq_predicate.rhs.type_;
+                write!(f, "{lhs} = {rhs}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for GenericParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &self.name;
+        match &self.kind {
+            GenericParamKind::Lifetime { outlives } if !outlives.is_empty() => {
+                let outlives = outlives.join(" + ");
+                write!(f, "{name}: {outlives}")
+            }
+            GenericParamKind::Type {
+                bounds, default, ..
+            } => {
+                write!(f, "{name}")?;
+                if !bounds.is_empty() {
+                    let bounds = format_bounds(bounds);
+                    write!(f, ": {bounds}")?;
+                }
+                match default {
+                    Some(default) => write!(f, " = {default}"),
+                    None => Ok(()),
+                }
+            }
+            GenericParamKind::Const { type_, default } => {
+                write!(f, "const {name}: {type_}")?;
+                match default {
+                    Some(default) => {
+                        let default = format_const_expr(default);
+                        write!(f, " = {default}")
+                    }
+                    None => Ok(()),
+                }
+            }
+            GenericParamKind::Lifetime { .. } => write!(f, "{name}"),
+        }
+    }
+}
+
+impl fmt::Display for Abi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, unwind) = match self {
+            Self::Rust => ("Rust", false),
+            Self::C { unwind } => ("C", *unwind),
+            Self::Cdecl { unwind } => ("cdecl", *unwind),
+            Self::Stdcall { unwind } => ("stdcall", *unwind),
+            Self::Fastcall { unwind } => ("fastcall", *unwind),
+            Self::Aapcs { unwind } => ("aapcs", *unwind),
+            Self::Win64 { unwind } => ("win64", *unwind),
+            Self::SysV64 { unwind } => ("sysv64", *unwind),
+            Self::System { unwind } => ("system", *unwind),
+            Self::Other(other) => (other.as_str(), false),
+        };
+        if unwind {
+            write!(f, "{name}-unwind")
+        } else {
+            write!(f, "{name}")
+        }
+    }
+}
+
+/// A placeholder for a type this crate doesn't support yet, naming the
+/// JSON key rustdoc encoded it under so it can be reported, e.g.
+/// `/* unsupported type: dyn_star */`. rustdoc's `_` for inferred types is
+/// the one such type with an obvious rendering.
+fn format_unknown_type(json: &Value) -> String {
+    let kind = match json {
+        Value::String(kind) if kind == "infer" => return "_".to_owned(),
+        Value::String(kind) => kind.as_str(),
+        Value::Object(object) =>
+            object.keys().next().map_or("unknown", String::as_str),
+        _ => "unknown",
+    };
+    format!("/* unsupported type: {kind} */")
+}
+
+/// Writes rustdoc's name for a primitive type as it's written in Rust. Only
+/// the never type differs: rustdoc calls it `never` rather than `!`. The unit
+/// type is an empty tuple rather than a primitive.
+fn format_primitive(primitive: &str) -> &str {
+    match primitive {
+        "never" => "!",
+        primitive => primitive,
+    }
+}
+
+impl fmt::Display for FunctionPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = &self.header;
+        if header.unsafe_ {
+            write!(f, "unsafe ")?;
+        }
+        if header.abi != Abi::Rust {
+            write!(f, "extern \"{}\" ", header.abi)?;
+        }
+
+        // Unnamed fn pointer params are recorded as `_`
+        let params = self
+            .decl
+            .inputs
+            .iter()
+            .map(|(name, param)| match name.as_str() {
+                "_" => param.to_string(),
+                _ => format!("{name}: {param}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "fn({params})")?;
+        if let Some(output) = &self.decl.output {
+            write!(f, " -> {output}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FunctionDecl {
+    /// Whether the first input is a `self` receiver, making this a method.
+    fn has_receiver(&self) -> bool {
+        self.inputs.first().is_some_and(|(name, _)| name == "self")
+    }
+
+    /// Formats the signature as `fn name(params) -> Output`. Unnamed params
+    /// are written as `_`, or as `arg0`, `arg1`, etc. by their position with
+    /// `positional_names`. With a `self_type`, the receiver is written out
+    /// with it in place of `Self`, as in a fully qualified call, e.g.
+    /// `self: &Tally<u32>` rather than `&self`.
+    fn format(
+        &self,
+        name: &str,
+        positional_names: bool,
+        self_type: Option<&str>,
+    ) -> String {
+        let mut params = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, (param_name, param))| match param_name.as_str() {
+                "self" => match self_type {
+                    Some(self_type) => {
+                        let param = replace_self(&param.to_string(), self_type);
+                        format!("self: {param}")
+                    }
+                    None => format_receiver(param),
+                },
+                "" | "_" if positional_names => format!("arg{i}: {param}"),
+                "" => format!("_: {param}"),
+                _ => format!("{param_name}: {param}"),
+            })
+            .collect::<Vec<_>>();
+        if self.c_variadic {
+            params.push("...".to_owned());
+        }
+        let params = params.join(", ");
+
+        match &self.output {
+            Some(ret) => format!("fn {name}({params}) -> {ret}"),
+            None => format!("fn {name}({params})"),
+        }
+    }
+}
+
+/// Formats a `self` param of type `param` as it's usually written, e.g.
+/// `&mut self` rather than `self: &mut Self`. Other receiver types, like
+/// `self: Box<Self>`, are written out in full.
+fn format_receiver(param: &Type) -> String {
+    let is_self = |param: &Type| matches!(param, Type::Generic { generic } if generic == "Self");
+    match param {
+        param if is_self(param) => "self".to_owned(),
+        Type::BorrowedRef { borrowed_ref } if is_self(&borrowed_ref.type_) => {
+            let lifetime = match &borrowed_ref.lifetime {
+                Some(lifetime) => format!("{lifetime} "),
+                None => String::new(),
+            };
+            let mut_ = if borrowed_ref.mutable { "mut " } else { "" };
+            format!("&{lifetime}{mut_}self")
+        }
+        param => format!("self: {param}"),
+    }
+}
+
+impl FunctionDecl {
+    /// Calls `visit` with each path in this signature, including nested ones
+    /// like the `Shape` in `Option<Shape>`.
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        for (_name, param) in &self.inputs {
+            param.visit_paths(visit);
+        }
+        if let Some(output) = &self.output {
+            output.visit_paths(visit);
+        }
+    }
+}
+
+impl Type {
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        match self {
+            Self::ResolvedPath { resolved_path } =>
+                resolved_path.visit_paths(visit),
+            Self::BorrowedRef { borrowed_ref } =>
+                borrowed_ref.type_.visit_paths(visit),
+            Self::Primitive { .. }
+            | Self::Generic { .. }
+            | Self::Unknown(_) => {}
+            Self::Qualified { qualified_path } => {
+                qualified_path.self_type.visit_paths(visit);
+                if let Some(args) = &qualified_path.args {
+                    args.visit_paths(visit);
+                }
+            }
+            Self::Array { array } => array.type_.visit_paths(visit),
+            Self::Tuple { tuple } =>
+                tuple.iter().for_each(|type_| type_.visit_paths(visit)),
+            Self::Slice { slice } => slice.visit_paths(visit),
+            Self::RawPointer { raw_pointer } =>
+                raw_pointer.type_.visit_paths(visit),
+            Self::ImplTrait { impl_trait } =>
+                impl_trait.iter().for_each(|bound| bound.visit_paths(visit)),
+            Self::DynTrait { dyn_trait } => dyn_trait
+                .traits
+                .iter()
+                .for_each(|trait_| trait_.trait_.visit_paths(visit)),
+            Self::FunctionPointer { function_pointer } =>
+                function_pointer.decl.visit_paths(visit),
+        }
+    }
+}
+
+impl ResolvedPath {
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        visit(self);
+        if let Some(args) = &self.args {
+            args.visit_paths(visit);
+        }
+    }
+}
+
+impl GenericArgs {
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        match self {
+            Self::AngleBracketed { angle_bracketed } => {
+                for arg in &angle_bracketed.args {
+                    if let GenericArg::Type { type_ } = arg {
+                        type_.visit_paths(visit);
+                    }
+                }
+                for binding in &angle_bracketed.bindings {
+                    match &binding.binding {
+                        BindingKind::Equality { equality } =>
+                            equality.type_.visit_paths(visit),
+                        BindingKind::Constraint { constraint } => constraint
+                            .iter()
+                            .for_each(|bound| bound.visit_paths(visit)),
+                    }
+                }
+            }
+            Self::Parenthesized { parenthesized } => {
+                for input in &parenthesized.inputs {
+                    input.visit_paths(visit);
+                }
+                if let Some(output) = &parenthesized.output {
+                    output.visit_paths(visit);
+                }
+            }
+        }
+    }
+}
+
+impl Generics {
+    /// Calls `visit` with each path in these params' bounds and defaults and
+    /// in the where clause.
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        for param in &self.params {
+            match &param.kind {
+                GenericParamKind::Lifetime { .. } => {}
+                GenericParamKind::Type {
+                    bounds, default, ..
+                } => {
+                    bounds.iter().for_each(|bound| bound.visit_paths(visit));
+                    if let Some(default) = default {
+                        default.visit_paths(visit);
+                    }
+                }
+                GenericParamKind::Const { type_, .. } =>
+                    type_.visit_paths(visit),
+            }
+        }
+        for predicate in &self.where_predicates {
+            match predicate {
+                WherePredicate::Bound { bound_predicate } => {
+                    bound_predicate.type_.visit_paths(visit);
+                    bound_predicate
+                        .bounds
+                        .iter()
+                        .for_each(|bound| bound.visit_paths(visit));
+                }
+                WherePredicate::Region { .. } => {}
+                WherePredicate::Eq { eq_predicate } =>
+                    eq_predicate.lhs.visit_paths(visit),
+            }
+        }
+    }
+}
+
+impl GenericBound {
+    fn visit_paths<'a>(&'a self, visit: &mut dyn FnMut(&'a ResolvedPath)) {
+        match self {
+            Self::TraitBound { trait_bound } =>
+                trait_bound.trait_.visit_paths(visit),
+            Self::Outlives { .. } | Self::Use { .. } => {}
+        }
+    }
+}
+
+impl GenericArg {
+    fn format(&self) -> String {
+        match self {
+            Self::Type { type_ } => type_.to_string(),
+            Self::Lifetime { lifetime } => lifetime.clone(),
+            Self::Const { const_ } =>
+                format_const_expr(&const_.expr).to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_type(f, self)
+    }
+}
+
+/// Writes `type_` as it's written in source, e.g. `&mut [u8]` or
+/// `impl Iterator<Item = u32>`, with nested types at most
+/// [`PrintOptions::max_type_depth`] deep.
+fn format_type(f: &mut fmt::Formatter<'_>, type_: &Type) -> fmt::Result {
+    write_nested(f, |f| match type_ {
+        Type::BorrowedRef { borrowed_ref } => {
+            if let Some(lt) = &borrowed_ref.lifetime {
+                write!(f, "&{} ", lt)?;
+            } else {
+                write!(f, "&")?;
+            }
+            if borrowed_ref.mutable {
+                write!(f, "mut ")?;
+            }
+            let type_ = &borrowed_ref.type_;
+            if type_.has_multiple_bounds() {
+                write!(f, "({type_})")
+            } else {
+                write!(f, "{type_}")
+            }
+        }
+        Type::Primitive { primitive } =>
+            write!(f, "{}", format_primitive(primitive)),
+        Type::Qualified { qualified_path } => write!(f, "{qualified_path}"),
+        Type::Generic { generic } => write!(f, "{}", generic),
+        Type::ResolvedPath { resolved_path } => {
+            write!(
+                f,
+                "{}{}",
+                resolved_path.name,
+                format_generic_args(resolved_path.args.as_ref())
+            )
+        }
+        Type::Tuple { tuple } => match tuple.as_slice() {
+            [type_] => write!(f, "({type_},)"),
+            types => {
+                let types = types
+                    .iter()
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "({types})")
+            }
+        },
+        Type::Slice { slice } => write!(f, "[{}]", slice),
+        Type::Array { array } => {
+            let len = &array.len;
+            write!(f, "[{}; {len}]", array.type_)
+        }
+        Type::RawPointer { raw_pointer } => write!(f, "{raw_pointer}"),
+        Type::ImplTrait { impl_trait } => {
+            let bounds = format_bounds(impl_trait);
+            write!(f, "impl {bounds}")
+        }
+        Type::DynTrait { dyn_trait } => write!(f, "{dyn_trait}"),
+        Type::FunctionPointer { function_pointer } =>
+            write!(f, "{function_pointer}"),
+        Type::Unknown(json) => f.write_str(&format_unknown_type(json)),
+    })
+}
+
+impl Type {
+    /// Whether this is a `dyn` or `impl` type with several bounds, which
+    /// must be parenthesized behind a reference, e.g. `&(dyn Error + Send)`.
+    fn has_multiple_bounds(&self) -> bool {
+        match self {
+            Self::ImplTrait { impl_trait } => impl_trait.len() > 1,
+            Self::DynTrait { dyn_trait } => dyn_trait.has_multiple_bounds(),
+            _ => false,
+        }
+    }
+}
+
+impl DynTrait {
+    fn has_multiple_bounds(&self) -> bool {
+        self.traits.len() + usize::from(self.lifetime.is_some()) > 1
+    }
+}
+
+impl fmt::Display for DynTrait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = self
+            .traits
+            .iter()
+            .map(TraitBound::to_string)
+            .chain(self.lifetime.clone())
+            .collect::<Vec<_>>()
+            .join(" + ");
+        write!(f, "dyn {bounds}")
+    }
+}
+
+impl fmt::Display for RawPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mutability = if self.mutable { "mut" } else { "const" };
+        let type_ = &self.type_;
+        if type_.has_multiple_bounds() {
+            write!(f, "*{mutability} ({type_})")
+        } else {
+            write!(f, "*{mutability} {type_}")
+        }
+    }
+}
+
+impl fmt::Display for QualifiedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let self_type = &self.self_type;
+        let name = &self.name;
+        let args = format_generic_args(self.args.as_ref());
+        match &self.trait_ {
+            // The trait's name is empty for shorthand like `T::Item`
+            Some(trait_) if !trait_.name.is_empty() => {
+                let trait_name = &trait_.name;
+                let trait_args = format_generic_args(trait_.args.as_ref());
+                write!(
+                    f,
+                    "<{self_type} as {trait_name}{trait_args}>::{name}{args}"
+                )
+            }
+            _ => write!(f, "{self_type}::{name}{args}"),
+        }
+    }
+}
+
+impl fmt::Display for TraitBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let binder = format_binder(&self.generic_params);
+        let modifier = self.modifier.prefix();
+        let name = &self.trait_.name;
+        let args = format_generic_args(self.trait_.args.as_ref());
+        write!(f, "{binder}{modifier}{name}{args}")
+    }
+}
+
+/// Formats a higher-ranked binder with a trailing space, e.g. the `for<'a> `
+/// in `for<'a> Fn(&'a str)`, or an empty string if there are no params.
+fn format_binder(params: &[GenericParam]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let params = params
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("for<{params}> ")
+}
+
+impl TraitBoundModifier {
+    /// The prefix for a bound with this modifier, e.g. the `?` in `?Sized`.
+    /// All bounds render through [`TraitBound`]'s `Display`, which uses this.
+    fn prefix(&self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Maybe => "?",
+            Self::MaybeConst => "~const ",
+        }
+    }
+}
+
+/// Displays the notice printed above a deprecated item, e.g.
+/// `**Deprecated** since 1.2.0: use `add` instead`.
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "**Deprecated**")?;
+        if let Some(since) = &self.since {
+            write!(f, " since {since}")?;
+        }
+        if let Some(note) = &self.note {
+            write!(f, ": {note}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GenericBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TraitBound { trait_bound } => write!(f, "{trait_bound}"),
+            Self::Outlives { outlives } => write!(f, "{outlives}"),
+            Self::Use { use_ } => {
+                let params = use_
+                    .iter()
+                    .map(|param| match param {
+                        CapturedParam::Name(name)
+                        | CapturedParam::Lifetime { lifetime: name }
+                        | CapturedParam::Param { param: name } => name.as_str(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "use<{params}>")
+            }
+        }
+    }
+}
+
+impl fmt::Display for TypeBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &self.name;
+        let args = format_generic_args(self.args.as_ref());
+        match &self.binding {
+            BindingKind::Equality { equality } => {
+                let type_ = &equality.type_;
+                write!(f, "{name}{args} = {type_}")
+            }
+            BindingKind::Constraint { constraint } => {
+                let bounds = format_bounds(constraint);
+                write!(f, "{name}{args}: {bounds}")
+            }
+        }
+    }
+}
+
+/// Joins bounds with ` + `, e.g. `Iterator<Item = u32> + 'a`.
+fn format_bounds(bounds: &[GenericBound]) -> String {
+    bounds
+        .iter()
+        .map(GenericBound::to_string)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Elides `expr`, a const or static's value, if it's longer than
+/// [`PrintOptions::max_const_value_chars`], e.g. `[0, 1, 2, ..]` becomes
+/// `[...]` and a long string literal becomes `"..."`.
+fn format_const_value<'a>(expr: &'a str, opts: &PrintOptions) -> Cow<'a, str> {
+    let is_short = opts
+        .max_const_value_chars
+        .is_none_or(|max_chars| expr.chars().count() <= max_chars);
+    if is_short {
+        return Cow::Borrowed(expr);
+    }
+    let marker = &opts.truncation_marker;
+    let brackets = [
+        ("[", "]"),
+        ("&[", "]"),
+        ("(", ")"),
+        ("{", "}"),
+        ("\"", "\""),
+        ("b\"", "\""),
+    ];
+    let enclosing = brackets
+        .iter()
+        .filter(|(open, close)| expr.starts_with(open) && expr.ends_with(close))
+        .max_by_key(|(open, _)| open.len());
+    match enclosing {
+        Some((open, close)) => Cow::Owned(format!("{open}{marker}{close}")),
+        None => Cow::Owned(marker.clone()),
+    }
+}
+
+/// Formats `time` as an RFC 3339 UTC timestamp, e.g. `2024-06-20T13:05:00Z`.
+/// Times before the Unix epoch are written as the epoch.
+fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    let (hour, minute, second) =
+        (secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60);
+
+    // The days since the epoch as a civil date, from Howard Hinnant's
+    // `civil_from_days`, with eras of 400 years starting on March 1st
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+/// Whether the item with this id passes [`PrintOptions::item_ids`].
+fn is_selected(item: &RustDocItem, opts: &PrintOptions) -> bool {
+    let id_selected = opts
+        .item_ids
+        .as_ref()
+        .is_none_or(|ids| ids.contains(&item.id));
+    let kind_selected = opts.include_kinds.as_ref().is_none_or(|kinds| {
+        item.kind().is_some_and(|kind| kinds.contains(&kind))
+    });
+    let deprecation_selected = !(opts.hide_deprecated && item.is_deprecated());
+    id_selected
+        && kind_selected
+        && deprecation_selected
+        && item.is_visible(opts)
+}
+
+/// Parses the output of `git diff --unified=0` into the ranges of lines
+/// added or modified in each file, as inclusive, 1-indexed `(first, last)`
+/// pairs. A deletion marks the lines on either side of it.
+fn parse_changed_lines(diff: &str) -> HashMap<String, Vec<(usize, usize)>> {
+    let mut changed_lines = HashMap::<String, Vec<(usize, usize)>>::new();
+    let mut file = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // Deleted files are `+++ /dev/null`
+            file = path.strip_prefix("b/").map(str::to_owned);
+            continue;
+        }
+        let (Some(file), Some(hunk)) = (&file, line.strip_prefix("@@ ")) else {
+            continue;
+        };
+        // e.g. `@@ -10,2 +12,3 @@ fn context()`: the new lines are 12..=14
+        let Some(new_range) = hunk
+            .split_whitespace()
+            .find_map(|range| range.strip_prefix('+'))
+        else {
+            continue;
+        };
+        let (start, count) = match new_range.split_once(',') {
+            Some((start, count)) => (start, count),
+            None => (new_range, "1"),
+        };
+        let (Ok(start), Ok(count)) = (start.parse(), count.parse::<usize>())
+        else {
+            continue;
+        };
+        let range = match count {
+            0 => (start, start + 1),
+            _ => (start, start + count - 1),
+        };
+        changed_lines.entry(file.clone()).or_default().push(range);
+    }
+    changed_lines
+}
+
+/// Whether `attr`, e.g. `#[serde(rename = "id")]`, is in [`ATTR_ALLOWLIST`].
+fn is_allowed_attr(attr: &str) -> bool {
+    let Some(content) = attr.strip_prefix("#[") else {
+        return false;
+    };
+    let name_end = content
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(content.len());
+    ATTR_ALLOWLIST.contains(&&content[..name_end])
+}
+
+/// Displays the length as written, followed by its value if that differs,
+/// e.g. `SIZE * 2 /* = 32 */`.
+impl fmt::Display for ArrayLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expr(expr) => write!(f, "{}", format_const_expr(expr)),
+            Self::Evaluated { expr, value } => {
+                let expr = format_const_expr(expr);
+                match value {
+                    Some(value) if value != expr =>
+                        write!(f, "{expr} /* = {value} */"),
+                    _ => write!(f, "{expr}"),
+                }
+            }
+        }
+    }
+}
+
+/// Normalizes a const expression such as an array length. rustdoc prints
+/// expressions it can't render from source, like `{ N + 1 }`, as `{ _ }`,
+/// which is printed as just `_`.
+fn format_const_expr(expr: &str) -> &str {
+    match expr {
+        "{ _ }" => "_",
+        _ => expr,
+    }
+}
+
+/// Displays a type one level deeper than the enclosing one with `write`, or
+/// as the [`PrintOptions::truncation_marker`] if that's deeper than the
+/// [`PrintOptions::max_type_depth`].
+fn write_nested(
+    f: &mut fmt::Formatter<'_>,
+    write: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    let depth = TYPE_DEPTH.get();
+    if depth >= MAX_TYPE_DEPTH.get() {
+        return TRUNCATION_MARKER.with_borrow(|marker| f.write_str(marker));
+    }
+    TYPE_DEPTH.set(depth + 1);
+    let result = write(f);
+    TYPE_DEPTH.set(depth);
+    result
+}
+
+/// Sets the [`PrintOptions::max_type_depth`] for types displayed on this
+/// thread until dropped.
+struct MaxTypeDepth {
+    previous: usize,
+}
+
+impl MaxTypeDepth {
+    fn set(max_type_depth: usize) -> Self {
+        let previous = MAX_TYPE_DEPTH.replace(max_type_depth);
+        Self { previous }
+    }
+}
+
+impl Drop for MaxTypeDepth {
+    fn drop(&mut self) {
+        MAX_TYPE_DEPTH.set(self.previous);
+    }
+}
+
+/// Sets the [`PrintOptions::truncation_marker`] for types displayed on this
+/// thread until dropped.
+struct TruncationMarker {
+    previous: String,
+}
+
+impl TruncationMarker {
+    fn set(truncation_marker: String) -> Self {
+        let previous = TRUNCATION_MARKER.replace(truncation_marker);
+        Self { previous }
+    }
+}
+
+impl Drop for TruncationMarker {
+    fn drop(&mut self) {
+        TRUNCATION_MARKER.set(std::mem::take(&mut self.previous));
+    }
+}
+
+/// Wraps `code` (which should end with a newline) in a code block tagged with
+/// [`PrintOptions::code_fence_lang`].
+fn format_code_fence(code: &str, opts: &PrintOptions) -> String {
+    let lang = opts.code_fence_lang.as_deref();
+    opts.format.renderer().code_block(code, lang)
+}
+
+/// The markup around items for an [`OutputFormat`]. Definitions and docs are
+/// the same in every format.
+trait Renderer {
+    /// The document's title, followed by a blank line.
+    fn title(&self, title: &str) -> String;
+    /// A top-level section heading, e.g. `Items`, followed by a blank line.
+    fn section(&self, heading: &str) -> String;
+    /// The line separating consecutive items, followed by a blank line.
+    fn separator(&self) -> &'static str;
+    /// A line marking the target of links to `#anchor`.
+    fn anchor(&self, anchor: &str) -> String;
+    /// The heading an item called `name` is printed under.
+    fn item_heading(&self, name: &str) -> String;
+    /// The heading a primitive type like `i32` is printed under.
+    fn primitive_heading(&self, name: &str) -> String;
+    /// A block of `code`, which should end with a newline.
+    fn code_block(&self, code: &str, lang: Option<&str>) -> String;
+    /// `code_block` under a label, e.g. `Trait implementations`.
+    fn labeled_block(&self, label: &str, code_block: &str) -> String;
+    /// A link reading `text` to the target marked with `anchor`.
+    fn link(&self, text: &str, anchor: &str) -> String;
+    /// An item of a bulleted list, followed by a newline.
+    fn list_item(&self, text: &str) -> String;
+
+    /// A bulleted list of consecutive `list_item`s.
+    fn list(&self, items: &str) -> String {
+        items.to_owned()
+    }
+
+    /// Plain `text`, escaped as needed to be embedded in this format.
+    fn text(&self, text: &str) -> String {
+        text.to_owned()
+    }
+
+    /// A paragraph of already rendered text, followed by a blank line.
+    fn paragraph(&self, text: &str) -> String {
+        format!("{text}\n\n")
+    }
+
+    /// What the document starts with, given its title and the names and
+    /// anchors of its items, e.g. an HTML page's `<head>`.
+    fn document_start(
+        &self,
+        _title: &str,
+        _items: &[(&str, String)],
+    ) -> String {
+        String::new()
+    }
+
+    /// What the document ends with, e.g. an HTML page's closing tags.
+    fn document_end(&self) -> &'static str {
+        ""
+    }
+}
+
+struct Markdown;
+
+impl Renderer for Markdown {
+    fn title(&self, title: &str) -> String {
+        format!("# {title}\n")
+    }
+
+    fn section(&self, heading: &str) -> String {
+        let underline = "-".repeat(heading.len());
+        format!("{heading}\n{underline}\n")
+    }
+
+    fn separator(&self) -> &'static str {
+        "---\n\n"
+    }
+
+    fn anchor(&self, anchor: &str) -> String {
+        format!("<a id=\"{anchor}\"></a>\n")
+    }
+
+    fn item_heading(&self, name: &str) -> String {
+        format!("`{name}`:")
+    }
+
+    fn primitive_heading(&self, name: &str) -> String {
+        format!("# primitive {name}")
+    }
+
+    fn code_block(&self, code: &str, lang: Option<&str>) -> String {
+        let lang = lang.unwrap_or_default();
+        format!("```{lang}\n{code}```\n")
+    }
+
+    fn labeled_block(&self, label: &str, code_block: &str) -> String {
+        format!("{label}:\n\n{code_block}")
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("[{text}](#{anchor})")
+    }
+
+    fn list_item(&self, text: &str) -> String {
+        format!("- {text}\n")
+    }
+}
+
+struct Plain;
+
+impl Renderer for Plain {
+    fn title(&self, title: &str) -> String {
+        format!("{title}\n")
+    }
+
+    fn section(&self, heading: &str) -> String {
+        format!("{heading}\n")
+    }
+
+    fn separator(&self) -> &'static str {
+        "\n"
+    }
+
+    fn anchor(&self, _anchor: &str) -> String {
+        String::new()
+    }
+
+    fn item_heading(&self, name: &str) -> String {
+        format!("{name}:")
+    }
+
+    fn primitive_heading(&self, name: &str) -> String {
+        format!("primitive {name}:")
+    }
+
+    fn code_block(&self, code: &str, _lang: Option<&str>) -> String {
+        code.lines()
+            .map(|line| match line {
+                "" => "\n".to_owned(),
+                line => format!("    {line}\n"),
+            })
+            .collect()
+    }
+
+    fn labeled_block(&self, label: &str, code_block: &str) -> String {
+        format!("{label}:\n\n{code_block}")
+    }
+
+    fn link(&self, text: &str, _anchor: &str) -> String {
+        text.to_owned()
+    }
+
+    fn list_item(&self, text: &str) -> String {
+        format!("- {text}\n")
+    }
+}
+
+struct AsciiDoc;
+
+impl Renderer for AsciiDoc {
+    fn title(&self, title: &str) -> String {
+        format!("= {title}\n")
+    }
+
+    fn section(&self, heading: &str) -> String {
+        format!("== {heading}\n")
+    }
+
+    fn separator(&self) -> &'static str {
+        "'''\n\n"
+    }
+
+    fn anchor(&self, anchor: &str) -> String {
+        format!("[[{anchor}]]\n")
+    }
+
+    fn item_heading(&self, name: &str) -> String {
+        format!("=== `{name}`")
+    }
+
+    fn primitive_heading(&self, name: &str) -> String {
+        format!("=== primitive `{name}`")
+    }
+
+    fn code_block(&self, code: &str, lang: Option<&str>) -> String {
+        let style = match lang {
+            Some(lang) => format!("[source,{lang}]\n"),
+            None => String::new(),
+        };
+        format!("{style}----\n{code}----\n")
+    }
+
+    fn labeled_block(&self, label: &str, code_block: &str) -> String {
+        format!(".{label}\n{code_block}")
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("<<{anchor},{text}>>")
+    }
+
+    fn list_item(&self, text: &str) -> String {
+        format!("* {text}\n")
+    }
+}
+
+struct Html;
+
+/// The stylesheet embedded in [`OutputFormat::Html`] pages.
+const HTML_STYLE: &str = "\
+body { display: flex; margin: 0; font-family: sans-serif; line-height: 1.5; }
+nav { position: sticky; top: 0; height: 100vh; overflow-y: auto; \
+flex: 0 0 16em; padding: 1em; background: #f5f5f5; font-size: 0.9em; }
+nav ul { list-style: none; padding: 0; }
+main { flex: 1; min-width: 0; max-width: 60em; padding: 1em 2em; }
+p { white-space: pre-wrap; }
+pre { padding: 0.75em; overflow-x: auto; background: #f5f5f5; }
+";
+
+impl Renderer for Html {
+    fn title(&self, title: &str) -> String {
+        let title = escape_html(title);
+        format!("<h1>{title}</h1>\n")
+    }
+
+    fn section(&self, heading: &str) -> String {
+        let heading = escape_html(heading);
+        format!("<h2>{heading}</h2>\n")
+    }
+
+    fn separator(&self) -> &'static str {
+        "<hr>\n\n"
+    }
+
+    fn anchor(&self, anchor: &str) -> String {
+        let anchor = escape_html(anchor);
+        format!("<a id=\"{anchor}\"></a>\n")
+    }
+
+    fn item_heading(&self, name: &str) -> String {
+        let name = escape_html(name);
+        format!("<h3><code>{name}</code></h3>")
+    }
+
+    fn primitive_heading(&self, name: &str) -> String {
+        let name = escape_html(name);
+        format!("<h3>primitive <code>{name}</code></h3>")
+    }
+
+    fn code_block(&self, code: &str, lang: Option<&str>) -> String {
+        let class = match lang {
+            Some(lang) => format!(" class=\"language-{}\"", escape_html(lang)),
+            None => String::new(),
+        };
+        let code = escape_html(code);
+        format!("<pre><code{class}>{code}</code></pre>\n")
+    }
+
+    fn labeled_block(&self, label: &str, code_block: &str) -> String {
+        let label = escape_html(label);
+        format!("<p>{label}:</p>\n{code_block}")
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        let text = escape_html(text);
+        let anchor = escape_html(anchor);
+        format!("<a href=\"#{anchor}\">{text}</a>")
+    }
+
+    fn list_item(&self, text: &str) -> String {
+        format!("<li>{text}</li>\n")
+    }
+
+    fn list(&self, items: &str) -> String {
+        format!("<ul>\n{items}</ul>\n")
+    }
+
+    fn text(&self, text: &str) -> String {
+        escape_html(text)
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        format!("<p>{text}</p>\n\n")
+    }
+
+    fn document_start(&self, title: &str, items: &[(&str, String)]) -> String {
+        let title = escape_html(title);
+        let toc = items
+            .iter()
+            .map(|(name, anchor)| self.list_item(&self.link(name, anchor)))
+            .collect::<String>();
+        let toc = self.list(&toc);
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n\
+             <meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n{HTML_STYLE}</style>\n</head>\n<body>\n\
+             <nav>\n{toc}</nav>\n<main>\n"
+        )
+    }
+
+    fn document_end(&self) -> &'static str {
+        "</main>\n</body>\n</html>\n"
+    }
+}
+
+/// Escapes `text` for use in HTML text or a quoted attribute value.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Rewrites each markdown link in `line`, e.g. `[text]` or `[text](dest)`,
+/// according to `resolve`, which is passed the link destination. `resolve`
+/// returns `None` to leave the link untouched, `Some(None)` to replace it
+/// with its text, or `Some(Some(anchor))` to link it to `anchor` with
+/// `renderer`.
+fn rewrite_doc_links(
+    line: &str,
+    resolve: impl Fn(&str) -> Option<Option<String>>,
+    renderer: &dyn Renderer,
+) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        out.push_str(&renderer.text(&rest[..open]));
+        rest = &rest[open..];
+        let Some(close) = rest.find(']') else { break };
+        let text = &rest[1..close];
+        let after_text = &rest[close + 1..];
+        let (dest, remaining) = match after_text.strip_prefix('(') {
+            Some(after_paren) => match after_paren.find(')') {
+                Some(end) => (&after_paren[..end], &after_paren[end + 1..]),
+                None => (text, after_text),
+            },
+            None => (text, after_text),
+        };
+
+        // A reference definition, e.g. `[text]: dest`
+        let resolved = if remaining.starts_with(':') {
+            None
+        } else {
+            resolve(dest)
+        };
+        match resolved {
+            Some(Some(anchor)) => out.push_str(&renderer.link(text, &anchor)),
+            Some(None) => out.push_str(&renderer.text(text)),
+            None => out.push_str(
+                &renderer.text(&rest[..rest.len() - remaining.len()]),
+            ),
+        }
+        rest = remaining;
+    }
+    out.push_str(&renderer.text(rest));
+    out
+}
+
+/// The body of the section of `docs` under the heading `title`, e.g. the
+/// lines under `# Returns`, without surrounding blank lines. The section ends
+/// at the next heading of the same or a higher level.
+fn doc_section<'a>(docs: &'a str, title: &str) -> Option<&'a str> {
+    let mut in_code_block = false;
+    let mut start = None;
+    let mut offset = 0;
+    for line in docs.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        if in_code_block {
+            continue;
+        }
+        let Some((level, text)) = markdown_heading(line.trim_end()) else {
+            continue;
+        };
+        match start {
+            None if text == title => start = Some((level, offset)),
+            Some((start_level, body_start)) if level <= start_level =>
+                return Some(docs[body_start..line_start].trim_matches('\n')),
+            _ => {}
+        }
+    }
+    let (_, body_start) = start?;
+    Some(docs[body_start..].trim_matches('\n'))
+}
+
+/// The level and text of a markdown heading like `## Title`.
+fn markdown_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (level > 0).then_some((level, text.trim()))
+}
+
+/// The first paragraph of `docs`, joined onto a single line.
+fn docs_summary(docs: &str) -> String {
+    docs.lines()
+        .map(str::trim)
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats the trait impls among `impl_ids`, one per line. Only impls written
+/// in this crate are included, not synthetic or blanket ones.
+fn format_trait_impls(
+    impl_ids: &[String],
+    doc: &RustDoc,
+    opts: &PrintOptions,
+) -> String {
+    impl_ids
+        .iter()
+        // Only impls from this crate (those starting with "0:")
+        .filter(|id| id.starts_with("0:"))
+        .filter_map(|id| doc.index.get(id))
+        .filter_map(|item| item.inner.as_ref()?.impl_.as_ref())
+        .filter(|impl_| impl_.trait_.is_some())
+        .filter(|impl_| !impl_.is_synthetic() && !impl_.is_blanket())
+        .map(|impl_| impl_.format(doc, opts, opts.expand_impl_items))
+        .collect()
+}
+
+/// Lists the auto traits implemented by the synthetic impls among `impl_ids`
+/// on one line, e.g. `Auto Trait Implementations: !Send, !Sync, Unpin`,
+/// sorted by trait. Conditional impls like `impl<T: Send> Send for Foo<T>`
+/// are listed as if they weren't. Of the auto traits a type doesn't
+/// implement, only the notable ones are listed.
+fn format_auto_trait_impls(
+    impl_ids: &[String],
+    doc: &RustDoc,
+) -> Option<String> {
+    // `Freeze` is an unstable, internal trait which some rustdocs include
+    const HIDDEN: &[&str] = &["Freeze"];
+    const NOTABLE_NEGATIVE: &[&str] = &["Send", "Sync", "Unpin"];
+
+    let mut auto_traits = impl_ids
+        .iter()
+        .filter_map(|id| doc.index.get(id))
+        .filter_map(|item| item.inner.as_ref()?.impl_.as_ref())
+        .filter(|impl_| impl_.is_synthetic())
+        .filter_map(|impl_| {
+            let name = impl_.trait_.as_ref()?.name.rsplit("::").next()?;
+            if HIDDEN.contains(&name) {
+                return None;
+            }
+            match impl_.negative {
+                false => Some((name, "")),
+                true if NOTABLE_NEGATIVE.contains(&name) => Some((name, "!")),
+                true => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    if auto_traits.is_empty() {
+        return None;
+    }
+    auto_traits.sort_unstable();
+    let auto_traits = auto_traits
+        .into_iter()
+        .map(|(name, negation)| format!("{negation}{name}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!("Auto Trait Implementations: {auto_traits}"))
+}
+
+/// Formats the inherent impls among `impl_ids` with all their items, as
+/// they're where a type's methods live. Like rustdoc, associated fns and
+/// methods are listed under separate `Functions:` and `Methods:` headings.
+fn format_inherent_impls(
+    impl_ids: &[String],
+    doc: &RustDoc,
+    opts: &PrintOptions,
+) -> String {
+    let inherent_impls = impl_ids
+        .iter()
+        .filter_map(|id| doc.index.get(id))
+        .filter_map(|item| item.inner.as_ref()?.impl_.as_ref())
+        .filter(|impl_| impl_.trait_.is_none())
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    for group in ImplItemGroup::ALL {
+        let impls = inherent_impls
+            .iter()
+            .filter_map(|impl_| {
+                impl_.format_items(doc, opts, |item| {
+                    ImplItemGroup::of(item) == group
+                })
+            })
+            .collect::<String>();
+        if !impls.is_empty() {
+            let code_block = format_code_fence(&impls, opts);
+            let labeled = opts
+                .format
+                .renderer()
+                .labeled_block(group.heading(), &code_block);
+            out.push_str(&format!("{labeled}\n"));
+        }
+    }
+    out
+}
+
+fn format_generic_args(args: Option<&GenericArgs>) -> String {
+    match args {
+        None => String::new(),
+        Some(GenericArgs::AngleBracketed { angle_bracketed }) => {
+            let formatted_args = angle_bracketed
+                .args
+                .iter()
+                .map(|arg| arg.format())
+                .chain(
+                    angle_bracketed.bindings.iter().map(TypeBinding::to_string),
+                )
+                .collect::<Vec<_>>();
+            if formatted_args.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", formatted_args.join(", "))
+            }
+        }
+        // `Fn(u32) -> bool`, `FnMut(u8)`, etc.
+        Some(GenericArgs::Parenthesized { parenthesized }) => {
+            let inputs = parenthesized
+                .inputs
+                .iter()
+                .map(|input| input.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            match &parenthesized.output {
+                Some(output) => format!("({inputs}) -> {output}"),
+                None => format!("({inputs})"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COMMON_JSON_STR: &str =
+        include_str!("../test-data/common/rustdoc.json");
+    const HEX_JSON_STR: &str = include_str!("../test-data/hex/rustdoc.json");
+    const FIXTURES_JSON_STR: &str =
+        include_str!("../test-data/fixtures/rustdoc.json");
+    const FIXTURES_JSON_BYTES: &[u8] =
+        include_bytes!("../test-data/fixtures/rustdoc.json");
+
+    fn fixtures_doc() -> RustDoc {
+        serde_json::from_str::<RustDoc>(FIXTURES_JSON_STR).unwrap()
+    }
+
+    /// Get the local fixture item with the given name.
+    fn fixture_item<'doc>(doc: &'doc RustDoc, name: &str) -> &'doc RustDocItem {
+        doc.index
+            .iter()
+            .filter(|(id, _)| id.starts_with("0:"))
+            .map(|(_, item)| item)
+            .find(|item| item.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("No fixture item named `{name}`"))
+    }
+
+    /// Get the function signature details of the fixture fn with the given
+    /// name.
+    fn fixture_fn<'doc>(doc: &'doc RustDoc, name: &str) -> &'doc FunctionDecl {
+        let item = fixture_item(doc, name);
+        let inner = item.inner.as_ref().unwrap();
+        &inner.function.as_ref().unwrap().decl
+    }
+
+    /// ```bash
+    /// $ cargo test print_hex_docs -- --ignored --nocapture
+    /// ```
+    #[test]
+    #[ignore]
+    fn print_hex_docs() {
+        // Parse into RustDoc struct first
+        let rust_doc = serde_json::from_str::<RustDoc>(HEX_JSON_STR).unwrap();
+
+        // Also parse as generic JSON for raw printing
+        let full_json = serde_json::from_str::<Value>(HEX_JSON_STR).unwrap();
+        let index_json =
+            full_json.get("index").and_then(Value::as_object).unwrap();
+
+        // Print all items from this crate using RustDoc's index
+        for (id, item) in &rust_doc.index {
+            if id.starts_with("0:") {
+                println!();
+                println!("=== Item ID: {id} ===");
+                println!();
+
+                // Get the raw JSON for this item
+                if let Some(item_json) = index_json.get(id) {
+                    println!("--- Raw JSON ---");
+                    let item_json_pretty =
+                        serde_json::to_string_pretty(item_json).unwrap();
+                    println!("{item_json_pretty}");
+                    println!();
+                }
+
+                println!("--- Formatted Output ---");
+                let opts = PrintOptions::default();
+                if let Some(markdown) = item.to_markdown(&rust_doc, &opts) {
+                    print!("{markdown}");
+                }
+                println!("=== End Item ===");
+            }
+        }
+    }
+
+    /// This test is designed for quickly debugging parsing errors.
+    ///
+    /// # Workflow
+    ///
+    /// ```bash
+    /// $ just iterate cargo test test_parse_individual
+    /// ```
+    ///
+    /// Query an advanced GPT with something like:
+    ///
+    /// ```
+    /// I'm working on a crate which parse rustdoc outputs. There's one item
+    /// it's getting stuck on, caught in the tests. There may be a bug
+    /// somewhere. Here's the code and test output which I think should contain
+    /// enough info for you to be able to diagnose the issue. Can you help? If
+    /// you spot any fixes, please indicate which sections should be modified
+    /// along with the exact code that it should be modified to. Don't forget to
+    /// update the `Display` implementations if a new enum variant was added.
+    ///
+    /// <code>
+    ///
+    /// <failed test output>
+    /// ```
+    #[test]
+    fn test_parse_individual() {
+        do_test(HEX_JSON_STR);
+        do_test(COMMON_JSON_STR);
+        do_test(FIXTURES_JSON_STR);
+
+        fn do_test(json_str: &str) {
+            // First parse as generic JSON
+            let json = serde_json::from_str::<Value>(json_str).unwrap();
+
+            // Get the index object
+            let index = json
+                .get("index")
+                .and_then(Value::as_object)
+                .expect("Expected 'index' to be an object");
+
+            let mut num_parsed = 0;
+
+            // Try to parse each item
+            for (id, item_value) in index {
+                match serde_json::from_value::<RustDocItem>(item_value.clone())
+                {
+                    Ok(_) => num_parsed += 1,
+                    Err(e) => {
+                        eprintln!("Failed to parse item {id}: {e}");
+                        eprintln!(
+                            "JSON: {}",
+                            serde_json::to_string_pretty(item_value).unwrap()
+                        );
+                        panic!("Failed after {num_parsed} parsed items");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let doc = serde_json::from_str::<RustDoc>(HEX_JSON_STR).unwrap();
+        doc.print();
+    }
+
+    #[test]
+    fn test_parenthesized_generic_args() {
+        let doc = fixtures_doc();
+        let output = |name| {
+            let decl = fixture_fn(&doc, name);
+            decl.output.as_ref().unwrap().to_string()
+        };
+
+        assert_eq!(output("boxed_closure"), "Box<dyn Fn(u32) -> bool>");
+        assert_eq!(
+            output("nested_closure"),
+            "Option<Box<dyn Fn(&str) -> Result<u8, ()>>>"
+        );
+        assert_eq!(output("unit_closure"), "Box<dyn FnMut(u8)>");
+        assert_eq!(output("lend_closure"), "T::Item<Box<dyn Fn(u8) -> bool>>");
+    }
+
+    #[test]
+    fn test_qualified_paths() {
+        let doc = fixtures_doc();
+        let output = |name| {
+            let decl = fixture_fn(&doc, name);
+            decl.output.as_ref().unwrap().to_string()
+        };
+
+        assert_eq!(output("through_ref"), "&<&T as std::ops::Deref>::Target");
+        assert_eq!(
+            output("bytes_into_iter"),
+            "<Vec<u8> as IntoIterator>::IntoIter"
+        );
+        assert_eq!(output("into_iter_of"), "<T as IntoIterator>::IntoIter");
+    }
+
+    #[test]
+    fn test_array_lengths() {
+        let doc = fixtures_doc();
+        let output = |name| {
+            let decl = fixture_fn(&doc, name);
+            decl.output.as_ref().unwrap().to_string()
+        };
+
+        // rustdoc evaluates lengths which don't depend on generics
+        assert_eq!(output("sized_buffer"), "[u8; 16]");
+        assert_eq!(output("doubled_buffer"), "[u8; 32]");
+        assert_eq!(output("block_buffer"), "[u8; N]");
+        assert_eq!(output("generic_buffer"), "[u8; N]");
+        assert_eq!(output("computed_holder"), "Holder<_>");
+        assert_eq!(format_const_expr("{ _ }"), "_");
+
+        // Newer formats may have both the expression and its value
+        let array = |len| {
+            let type_ = json!({ "array": { "type": { "primitive": "u8" }, "len": len } });
+            serde_json::from_value::<Type>(type_).unwrap().to_string()
+        };
+        assert_eq!(
+            array(json!({ "expr": "SIZE * 2", "value": "32" })),
+            "[u8; SIZE * 2 /* = 32 */]"
+        );
+        assert_eq!(array(json!({ "expr": "16", "value": "16" })), "[u8; 16]");
+        assert_eq!(array(json!({ "expr": "N", "value": null })), "[u8; N]");
+    }
+
+    #[test]
+    fn test_multi_bound_dyn_behind_ref() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+
+        assert_eq!(
+            signature("report_error"),
+            "fn report_error(error: &(dyn Error + Send)) -> String"
+        );
+        assert_eq!(
+            signature("report_any_error"),
+            "fn report_any_error(error: &dyn Error) -> String"
+        );
+        assert_eq!(
+            signature("boxed_error"),
+            "fn boxed_error() -> Box<dyn Error + Send + Sync + 'static>"
+        );
+        assert_eq!(
+            signature("displayable"),
+            "fn displayable<'a>(value: &'a u32) -> &'a (dyn Display + 'a)"
+        );
+    }
+
+    #[test]
+    fn test_generic_array_and_slice_elements() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+
+        assert_eq!(
+            signature("first_half"),
+            "fn first_half<T, const N: usize>(arr: &[T; N]) -> &[T]"
+        );
+        assert_eq!(
+            signature("chunks_of"),
+            "fn chunks_of<T: Copy, const N: usize>(items: &[T]) -> Vec<[T; N]>"
+        );
+    }
+
+    #[test]
+    fn test_fn_traits_in_dyn_and_impl() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+
+        assert_eq!(
+            signature("string_source"),
+            "fn string_source() -> Box<dyn FnMut() -> String>"
+        );
+        // The synthetic generic param behind `impl Trait` isn't printed
+        assert_eq!(
+            signature("with_greeting"),
+            "fn with_greeting(callback: impl FnOnce(&str))"
+        );
+    }
+
+    #[test]
+    fn test_impl_assoc_items() {
+        let doc = fixtures_doc();
+        let shape = fixture_item(&doc, "Shape");
+        let impls =
+            &shape.inner.as_ref().unwrap().enum_.as_ref().unwrap().impls;
+
+        let compact = format_trait_impls(impls, &doc, &PrintOptions::default());
+        assert!(compact.contains("impl HasId for Shape { ... }\n"));
+        assert!(compact.contains("impl Clone for Shape { ... }\n"));
+
+        let opts = PrintOptions {
+            truncation_marker: "…".to_owned(),
+            ..Default::default()
+        };
+        let unicode = format_trait_impls(impls, &doc, &opts);
+        assert!(unicode.contains("impl Clone for Shape { … }\n"));
+
+        let opts = PrintOptions {
+            expand_impl_items: true,
+            ..Default::default()
+        };
+        let expanded = format_trait_impls(impls, &doc, &opts);
+        let expected = "\
+impl HasId for Shape {
+    const ID: u32 = 7;
+    type Repr = String;
+}
+";
+        assert!(expanded.contains(expected), "{expanded}");
+    }
+
+    #[test]
+    fn test_api_summary() {
+        let doc = fixtures_doc();
+        let summary = doc.summary(&PrintOptions::default());
+
+        let add = &summary["fixtures::add"];
+        assert_eq!(add.kind, RustDocItemKind::Function);
+        assert_eq!(
+            add.signature.as_deref(),
+            Some("fn add(a: u32, b: u32) -> u32")
+        );
+        assert_eq!(add.docs_summary.as_deref(), Some("Adds two numbers."));
+        assert!(!add.deprecated);
+        assert!(summary["fixtures::sub"].deprecated);
+
+        assert_eq!(summary["fixtures::Shape"].kind, RustDocItemKind::Enum);
+        assert!(summary.get("fixtures::missing").is_none());
+
+        // Items are sorted by path
+        let paths = summary
+            .into_iter()
+            .map(|item| item.path)
+            .collect::<Vec<_>>();
+        let mut sorted = paths.clone();
+        sorted.sort();
+        assert_eq!(paths, sorted);
+
+        let opts = PrintOptions {
+            public_only: true,
+            hide_deprecated: true,
+            ..PrintOptions::default()
+        };
+        assert!(paths.iter().any(|path| path == "fixtures::private_helper"));
+        let filtered = doc.summary(&opts);
+        assert!(filtered.get("fixtures::add").is_some());
+        assert!(filtered.get("fixtures::sub").is_none());
+        assert!(filtered.get("fixtures::private_helper").is_none());
+    }
+
+    #[test]
+    fn test_impl_trait_bounds() {
+        let doc = fixtures_doc();
+
+        let iter_numbers = fixture_fn(&doc, "iter_numbers");
+        assert_eq!(
+            iter_numbers.output.as_ref().unwrap().to_string(),
+            "impl Iterator<Item = u32> + 'a"
+        );
+
+        let sum_all = fixture_fn(&doc, "sum_all");
+        let (_, values) = &sum_all.inputs[0];
+        assert_eq!(values.to_string(), "impl IntoIterator<Item = u32> + Clone");
+    }
+
+    #[test]
+    fn test_inline_reexport() {
+        let doc = fixtures_doc();
+        let import = doc
+            .index
+            .values()
+            .filter_map(|item| item.inner.as_ref()?.import.as_ref())
+            .find(|import| import.name == "multiply")
+            .unwrap();
+
+        let (target, source) = import.resolve(&doc).unwrap();
+        assert_eq!(source, "fixtures::inner::mul");
+        let code_block = target
+            .format_code_block(
+                &import.name,
+                Some(&source),
+                &doc,
+                &PrintOptions::default(),
+            )
+            .unwrap();
+        let expected = "\
+```rust
+/* re-exported from fixtures::inner::mul */
+pub fn multiply(a: u32, b: u32) -> u32;
+```
+";
+        assert_eq!(code_block, expected);
+    }
+
+    #[test]
+    fn test_extern_items() {
+        let doc = fixtures_doc();
+        let code_block = |name| {
+            let item = fixture_item(&doc, name);
+            item.format_code_block(name, None, &doc, &PrintOptions::default())
+                .unwrap()
+        };
+
+        assert!(fixture_item(&doc, "abs").is_foreign());
+        assert_eq!(
+            code_block("abs"),
+            "```rust\nextern \"C\" {\n    pub fn abs(input: i32) -> i32;\n}\n```\n"
+        );
+        assert_eq!(
+            code_block("printf"),
+            "```rust\nextern \"C\" {\n    pub fn printf(format: *const u8, ...) -> i32;\n}\n```\n"
+        );
+        assert_eq!(
+            code_block("errno"),
+            "```rust\nextern {\n    pub static errno: i32;\n}\n```\n"
+        );
+
+        // Rust fns with a C ABI are defined normally
+        assert!(!fixture_item(&doc, "callback").is_foreign());
+        assert_eq!(
+            code_block("callback"),
+            "```rust\npub extern \"C\" fn callback(value: u32) -> u32;\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_higher_ranked_bounds() {
+        let doc = fixtures_doc();
+        let output = |name| {
+            let decl = fixture_fn(&doc, name);
+            decl.output.as_ref().unwrap().to_string()
+        };
+
+        assert_eq!(output("str_predicate"), "impl for<'a> Fn(&'a str) -> bool");
+        assert_eq!(
+            output("boxed_str_predicate"),
+            "Box<dyn for<'a> Fn(&'a str) -> bool>"
+        );
+    }
+
+    #[test]
+    fn test_tree_json() {
+        let doc = fixtures_doc();
+        let tree = doc.to_tree_json();
+        assert_eq!(tree["name"], "fixtures");
+        assert_eq!(tree["kind"], "module");
+
+        let child = |node: &Value, name: &str| {
+            let items = node["items"].as_array().unwrap();
+            items
+                .iter()
+                .find(|item| item["name"] == name)
+                .unwrap_or_else(|| panic!("No child named `{name}`"))
+                .clone()
+        };
+
+        let inner = child(&tree, "inner");
+        assert_eq!(inner["path"], "fixtures::inner");
+        let mul = child(&inner, "mul");
+        assert_eq!(mul["kind"], "function");
+        assert_eq!(mul["signature"], "pub fn mul(a: u32, b: u32) -> u32;");
+        assert_eq!(mul["docs"], "Multiplies two numbers.");
+
+        let multiply = child(&tree, "multiply");
+        assert_eq!(multiply["kind"], "import");
+
+        let shape = child(&tree, "Shape");
+        let signature = shape["signature"].as_str().unwrap();
+        assert!(signature.starts_with("pub enum Shape {"));
+        assert!(tree.to_string().contains("\"private_helper\""));
+
+        let opts = PrintOptions {
+            public_only: true,
+            ..PrintOptions::default()
+        };
+        let public_tree = doc.to_tree_json_with(&opts);
+        let public_tree_str = public_tree.to_string();
+        assert!(!public_tree_str.contains("\"private_helper\""));
+        let inner = child(&public_tree, "inner");
+        child(&inner, "mul");
+
+        // Modules are kept for the items in them, even if not selected
+        let opts = PrintOptions {
+            include_kinds: Some(HashSet::from([RustDocItemKind::Function])),
+            ..PrintOptions::default()
+        };
+        let fn_tree = doc.to_tree_json_with(&opts);
+        let inner = child(&fn_tree, "inner");
+        child(&inner, "mul");
+        assert!(!fn_tree.to_string().contains("\"Shape\""));
+    }
+
+    #[test]
+    fn test_code_fence_lang() {
+        let doc = fixtures_doc();
+        let add = fixture_item(&doc, "add");
+        let code_block = |code_fence_lang: Option<&str>| {
+            let opts = PrintOptions {
+                code_fence_lang: code_fence_lang.map(str::to_owned),
+                ..Default::default()
+            };
+            add.format_code_block("add", None, &doc, &opts).unwrap()
+        };
+
+        let signature = "pub fn add(a: u32, b: u32) -> u32;";
+        assert_eq!(
+            code_block(Some("rust")),
+            format!("```rust\n{signature}\n```\n")
+        );
+        assert_eq!(
+            code_block(Some("rs")),
+            format!("```rs\n{signature}\n```\n")
+        );
+        assert_eq!(code_block(None), format!("```\n{signature}\n```\n"));
+    }
+
+    #[test]
+    fn test_where_clause_maybe_bound() {
+        let doc = fixtures_doc();
+        let item = fixture_item(&doc, "size_of_unsized");
+        assert_eq!(
+            item.signature().unwrap(),
+            "fn size_of_unsized<T>(value: &T) -> usize where T: ?Sized"
+        );
+    }
+
+    #[test]
+    fn test_embedding_docs() {
+        let doc = fixtures_doc();
+        let embedding_docs = doc.to_embedding_docs(&PrintOptions::default());
+        let embedding_doc = |id: &str| {
+            embedding_docs
+                .iter()
+                .find(|embedding_doc| embedding_doc.id == id)
+        };
+
+        let mul = embedding_doc("fixtures::inner::mul").unwrap();
+        assert_eq!(
+            mul.text,
+            "fn mul(a: u32, b: u32) -> u32\n\nMultiplies two numbers."
+        );
+        assert_eq!(mul.metadata.kind, RustDocItemKind::Function);
+        assert_eq!(mul.metadata.module, "fixtures::inner");
+        assert!(!mul.metadata.deprecated);
+        assert!(embedding_doc("fixtures::sub").unwrap().metadata.deprecated);
+
+        // Private items are excluded
+        assert!(embedding_doc("fixtures::private_helper").is_none());
+        assert!(fixture_item(&doc, "private_helper").docs.is_some());
+
+        let json_line = serde_json::to_string(mul).unwrap();
+        assert!(!json_line.contains('\n'));
+
+        let opts = PrintOptions {
+            include_kinds: Some(HashSet::from([RustDocItemKind::Enum])),
+            ..PrintOptions::default()
+        };
+        let enum_docs = doc.to_embedding_docs(&opts);
+        assert!(!enum_docs.is_empty());
+        let is_enum = |embedding_doc: &EmbeddingDoc| {
+            embedding_doc.metadata.kind == RustDocItemKind::Enum
+        };
+        assert!(enum_docs.iter().all(is_enum));
+    }
+
+    #[test]
+    fn test_method_generics() {
+        let doc = fixtures_doc();
+        let wrapper = fixture_item(&doc, "Wrapper");
+        let impl_ids = wrapper.impl_ids(&PrintOptions::default()).unwrap();
+
+        let inherent_impls =
+            format_inherent_impls(impl_ids, &doc, &PrintOptions::default());
+        assert_eq!(
+            inherent_impls,
+            "\
+Functions:
+
+```rust
+impl<T> Wrapper<T> {
+    pub fn new(value: T) -> Self;
+}
+```
+
+Methods:
+
+```rust
+impl<T> Wrapper<T> {
+    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Wrapper<U>;
+}
+```
+
+"
+        );
+
+        let definition = wrapper
+            .format_definition("Wrapper", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "pub struct Wrapper<T> {\n    /// The wrapped value.\n    pub value: T,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_doc_links_to_anchors() {
+        let doc = fixtures_doc();
+        let double = fixture_item(&doc, "double");
+        let docs = double.docs.as_deref().unwrap();
+        let opts = PrintOptions::default();
+        assert_eq!(
+            double.format_docs(docs, &doc, &opts),
+            "\
+Doubles `x`, like [`add`](#add)ing it to itself or [multiplying by two](#innermul).
+Unlike `undocumented`, it has docs.
+
+```
+let v = [1, 2];
+assert_eq!(v[0], 1);
+```"
+        );
+
+        let opts = PrintOptions {
+            raw_docs: true,
+            ..PrintOptions::default()
+        };
+        assert_eq!(double.format_docs(docs, &doc, &opts), docs);
+
+        // A custom transform runs before links are rewritten
+        let opts = PrintOptions {
+            doc_transform: Some(DocTransform::new(|docs| {
+                docs.lines().take(1).collect::<String>()
+            })),
+            ..PrintOptions::default()
+        };
+        assert_eq!(
+            double.format_docs(docs, &doc, &opts),
+            "Doubles `x`, like [`add`](#add)ing it to itself or \
+             [multiplying by two](#innermul)."
+        );
+
+        // Links which aren't intra-doc links are left alone
+        let resolve = |_: &str| None;
+        let line = "See [the book](https://doc.rust-lang.org/book/).";
+        assert_eq!(rewrite_doc_links(line, resolve, &Markdown), line);
+    }
+
+    #[test]
+    fn test_primitive() {
+        let doc = fixtures_doc();
+        let i32_ = fixture_item(&doc, "i32");
+        let opts = PrintOptions::default();
+        assert_eq!(i32_.format_heading("i32", &opts), "# primitive i32");
+        assert!(i32_.impl_ids(&PrintOptions::default()).is_none());
+
+        let opts = PrintOptions {
+            expand_impl_items: true,
+            ..Default::default()
+        };
+        let impl_ids = i32_.impl_ids(&opts).unwrap();
+        assert_eq!(
+            format_trait_impls(impl_ids, &doc, &opts),
+            "\
+impl HasId for i32 {
+    const ID: u32 = 32;
+    type Repr = i32;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_dropped_items() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+        let dropped = doc.dropped_items(&opts);
+
+        let undocumented = fixture_item(&doc, "undocumented");
+        assert!(dropped.contains(&undocumented.id));
+        // Documented items and the members of impls aren't reported
+        assert!(!dropped.contains(&fixture_item(&doc, "double").id));
+        assert!(!dropped.contains(&fixture_item(&doc, "map").id));
+
+        // Re-exports are dropped unless inlined
+        let import_id = doc
+            .index
+            .values()
+            .find(|item| {
+                let import =
+                    item.inner.as_ref().and_then(|i| i.import.as_ref());
+                import.is_some_and(|import| import.name == "multiply")
+            })
+            .map(|item| item.id.clone())
+            .unwrap();
+        assert!(dropped.contains(&import_id));
+        let opts = PrintOptions {
+            inline_reexports: true,
+            ..Default::default()
+        };
+        assert!(!doc.dropped_items(&opts).contains(&import_id));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let doc = fixtures_doc();
+        let double = fixture_item(&doc, "double");
+        assert_eq!(double.name(), Some("double"));
+        // Intra-doc links are left as written
+        let docs = double.docs().unwrap();
+        assert!(docs.starts_with("Doubles `x`, like [`add`]ing it"));
+        assert_eq!(fixture_item(&doc, "undocumented").docs(), None);
+    }
+
+    #[test]
+    fn test_item_markdown() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+
+        let wrapper = doc.item_markdown("fixtures::Wrapper", &opts).unwrap();
+        assert!(wrapper.contains("pub struct Wrapper<T> {"));
+        assert!(wrapper.contains("impl<T> Wrapper<T> {"));
+
+        // Methods aren't in the `paths` map, so they're found by name
+        let map = doc.item_markdown("fixtures::Wrapper::map", &opts).unwrap();
+        assert!(map.contains("Applies `f` to the wrapped value."));
+
+        assert_eq!(
+            doc.item_markdown("fixtures::nonexistent", &opts),
+            Err(LookupError::NotFound("fixtures::nonexistent".to_owned()))
+        );
+        assert_eq!(
+            doc.item_markdown("fixtures::undocumented", &opts),
+            Err(LookupError::NotRendered(
+                "fixtures::undocumented".to_owned()
+            ))
+        );
+        // `ID` is the name of both `HasId`'s assoc const and those of its impls
+        let Err(LookupError::Ambiguous { candidates, .. }) =
+            doc.item_markdown("ID", &opts)
+        else {
+            panic!("Expected `ID` to be ambiguous");
+        };
+        assert!(candidates.len() > 1);
+    }
+
+    #[test]
+    fn test_inline_small_enums() {
+        let doc = fixtures_doc();
+        let describe_shape = fixture_item(&doc, "describe_shape");
+        let inlined = |max_variants| {
+            let opts = PrintOptions {
+                inline_enums_max_variants: max_variants,
+                ..Default::default()
+            };
+            describe_shape.format_inlined_enums(&doc, &opts)
+        };
+
+        let shape = "\
+pub enum Shape {
+    /// A circle.
+    Circle,
+    /// A square.
+    Square,
+}
+";
+        assert_eq!(inlined(Some(2)), shape);
+        assert_eq!(inlined(Some(1)), "");
+        assert_eq!(inlined(None), "");
+    }
+
+    #[test]
+    fn test_where_clauses_on_type_aliases() {
+        let doc = fixtures_doc();
+        let definition = |name| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, &PrintOptions::default())
+                .unwrap()
+        };
+
+        assert_eq!(
+            definition("Pairs"),
+            "pub type Pairs<T> where T: Clone = Vec<(T, T)>;\n"
+        );
+        assert_eq!(
+            definition("Container"),
+            "\
+pub trait Container {
+    type Item<T>: Clone where T: Clone;
+    fn first<T: Clone>(&self) -> Option<Self::Item<T>>;
+}
+"
+        );
+
+        let opts = PrintOptions {
+            expand_impl_items: true,
+            ..Default::default()
+        };
+        let holder = fixture_item(&doc, "Holder");
+        let impl_ids = holder.impl_ids(&opts).unwrap();
+        assert_eq!(
+            format_trait_impls(impl_ids, &doc, &opts),
+            "\
+impl<const N: usize> Container for Holder<N> {
+    type Item<T> = Vec<T> where T: Clone;
+    fn first<T: Clone>(&self) -> Option<Self::Item<T>>;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_blanket_impls() {
+        let doc = fixtures_doc();
+        let blanket_impls = doc.format_blanket_impls(&PrintOptions::default());
+        assert!(blanket_impls.contains("impl<T> From<T> for T { ... }\n"));
+        assert!(blanket_impls
+            .contains("impl<T> ToOwned for T where T: Clone { ... }\n"));
+        // Each blanket impl is listed once, although it applies to many types
+        assert_eq!(blanket_impls.matches(" From<T> for T ").count(), 1);
+
+        // ... and isn't listed under each type
+        let shape = fixture_item(&doc, "Shape");
+        let impl_ids = shape.impl_ids(&PrintOptions::default()).unwrap();
+        let trait_impls =
+            format_trait_impls(impl_ids, &doc, &PrintOptions::default());
+        assert!(!trait_impls.contains("From<T>"));
+    }
+
+    #[test]
+    fn test_variant_attrs() {
+        let doc = fixtures_doc();
+        let level = fixture_item(&doc, "Level");
+        let definition = |variant_attrs| {
+            let opts = PrintOptions {
+                variant_attrs,
+                ..Default::default()
+            };
+            level.format_definition("Level", &doc, &opts).unwrap()
+        };
+
+        assert_eq!(
+            definition(true),
+            "\
+pub enum Level {
+    /// Everything.
+    Debug,
+    /// The default level.
+    #[default]
+    Info,
+    /// Problems only.
+    #[non_exhaustive]
+    Error,
+}
+"
+        );
+        assert!(!definition(false).contains("#["));
+
+        assert!(is_allowed_attr("#[serde(rename = \"id\")]"));
+        assert!(!is_allowed_attr("#[serde_with::skip]"));
+        assert!(!is_allowed_attr("#[allow(dead_code)]"));
+    }
+
+    #[test]
+    fn test_public_api_hash() {
+        let hash = fixtures_doc().public_api_hash();
+        assert_eq!(fixtures_doc().public_api_hash(), hash);
+
+        // Docs don't affect the hash
+        let mut doc = fixtures_doc();
+        let add_id = fixture_item(&doc, "add").id.clone();
+        doc.index.get_mut(&add_id).unwrap().docs = Some("Changed.".to_owned());
+        assert_eq!(doc.public_api_hash(), hash);
+
+        // ... but paths do
+        let summary = doc.paths.get_mut(&add_id).unwrap();
+        summary.path = vec!["fixtures".to_owned(), "plus".to_owned()];
+        assert_ne!(doc.public_api_hash(), hash);
+    }
+
+    #[test]
+    fn test_tuple_and_unit_structs() {
+        let doc = fixtures_doc();
+        let definition = |name| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, &PrintOptions::default())
+                .unwrap()
+        };
+
+        assert_eq!(
+            definition("Meters"),
+            "pub struct Meters(pub u32, String);\n"
+        );
+        assert_eq!(definition("Marker"), "pub struct Marker;\n");
+
+        // Without `--document-private-items`, private fields are stripped
+        let stripped = serde_json::from_value::<StructDetails>(json!({
+            "kind": { "tuple": [null, null] },
+            "generics": { "params": [], "where_predicates": [] },
+            "impls": [],
+        }))
+        .unwrap();
+        assert_eq!(
+            stripped.format("Meters", &doc, &PrintOptions::default()),
+            "pub struct Meters(_, _);\n"
+        );
+    }
+
+    #[test]
+    fn test_changed_lines() {
+        let diff = "\
+diff --git a/test-data/fixtures/lib.rs b/test-data/fixtures/lib.rs
+index 1111111..2222222 100644
+--- a/test-data/fixtures/lib.rs
++++ b/test-data/fixtures/lib.rs
+@@ -65 +65 @@ pub fn add(a: u32, b: u32) -> u32 {
+-    a + b
++    b + a
+@@ -100,2 +99,0 @@ pub fn sub(a: u32, b: u32) -> u32 {
+diff --git a/old.rs b/old.rs
+deleted file mode 100644
+--- a/old.rs
++++ /dev/null
+@@ -1,3 +0,0 @@
+";
+        let changed_lines = parse_changed_lines(diff);
+        assert_eq!(
+            changed_lines,
+            HashMap::from([(
+                "test-data/fixtures/lib.rs".to_owned(),
+                vec![(65, 65), (99, 100)],
+            )])
+        );
+
+        let doc = fixtures_doc();
+        let changed = doc.items_in_changed_lines(&changed_lines);
+        assert!(changed.contains(&fixture_item(&doc, "add").id));
+        assert!(!changed.contains(&fixture_item(&doc, "double").id));
+
+        let opts = PrintOptions {
+            item_ids: Some(changed),
+            ..Default::default()
+        };
+        assert!(doc.is_rendered(&fixture_item(&doc, "add").id, &opts));
+        assert!(!doc.is_rendered(&fixture_item(&doc, "double").id, &opts));
+    }
+
+    #[test]
+    fn test_unsupported_kinds() {
+        let doc = fixtures_doc();
+        let kinds = doc.unsupported_kinds();
+        assert_eq!(kinds.get("macro"), Some(&1));
+        assert!(!kinds.contains_key("function"));
+        assert!(!kinds.contains_key("struct"));
+    }
+
+    #[test]
+    fn test_mixed_generic_params() {
+        let doc = fixtures_doc();
+        let markdown = fixture_item(&doc, "Ref")
+            .format_definition("Ref", &doc, &PrintOptions::default())
+            .unwrap();
+        let expected = "pub struct Ref<'a, 'b: 'a, T: 'a + ?Sized, \
+            const N: usize, U = u8> {";
+        assert!(markdown.contains(expected), "{markdown}");
+    }
+
+    #[test]
+    fn test_async_trait_methods() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Fetch")
+            .format_definition("Fetch", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "pub trait Fetch {\n    \
+                async fn fetch(&self) -> Vec<u8>;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_const_generic_defaults() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Buf")
+            .format_definition("Buf", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "pub struct Buf<T = u8, const N: usize = 1024> {\n    \
+                pub data: [T; N],\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let doc = fixtures_doc();
+        let tree = doc.module_tree();
+        assert_eq!(tree.name, "fixtures");
+        let module_names = tree
+            .modules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(module_names, ["inner", "prim_i32", "prelude", "private"]);
+        let inner = &tree.modules[0];
+        let item_names = inner
+            .item_ids
+            .iter()
+            .filter_map(|id| doc.item(id)?.name())
+            .collect::<Vec<_>>();
+        assert!(item_names.contains(&"mul"), "{item_names:?}");
+        assert!(tree.item_ids.iter().all(|id| doc.item(id).is_some()));
+
+        // A root module without `items`
+        let doc = serde_json::from_value::<RustDoc>(json!({
+            "root": "0:0",
+            "crate_version": "0.1.0",
+            "includes_private": false,
+            "index": {
+                "0:0": {
+                    "id": "0:0",
+                    "name": "empty",
+                    "visibility": "public",
+                    "docs": null,
+                    "inner": {
+                        "module": { "is_crate": true, "is_stripped": false },
+                    },
+                },
+            },
+            "paths": {},
+        }))
+        .unwrap();
+        let tree = doc.module_tree();
+        assert_eq!(tree.name, "empty");
+        assert!(tree.modules.is_empty() && tree.item_ids.is_empty());
+    }
+
+    #[test]
+    fn test_trait_aliases() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Task")
+            .format_definition("Task", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "pub trait Task<T> = Iterator<Item = T> + Send;\n"
+        );
+        assert!(!doc.unsupported_kinds().contains_key("trait_alias"));
+    }
+
+    #[test]
+    fn test_include_kinds() {
+        let doc = fixtures_doc();
+        assert_eq!(
+            fixture_item(&doc, "add").kind(),
+            Some(RustDocItemKind::Function)
+        );
+        assert_eq!(
+            fixture_item(&doc, "SIZE").kind(),
+            Some(RustDocItemKind::Constant)
+        );
+
+        let opts = PrintOptions {
+            include_kinds: Some(HashSet::from([RustDocItemKind::Trait])),
+            ..PrintOptions::default()
+        };
+        let add = fixture_item(&doc, "add");
+        let fetch = fixture_item(&doc, "Fetch");
+        assert!(!doc.is_rendered(&add.id, &opts));
+        assert!(doc.is_rendered(&fetch.id, &opts));
+        assert!(doc.is_rendered(&add.id, &PrintOptions::default()));
+    }
+
+    #[test]
+    fn test_qualified_names() {
+        let doc = fixtures_doc();
+        let mul = doc.find_by_path("fixtures::inner::mul")[0];
+        let opts = PrintOptions {
+            qualified_names: true,
+            ..PrintOptions::default()
+        };
+        let markdown = mul.to_markdown(&doc, &opts).unwrap();
+        assert!(markdown.contains("`inner::mul`:\n"), "{markdown}");
+        let markdown = mul.to_markdown(&doc, &PrintOptions::default()).unwrap();
+        assert!(markdown.contains("`mul`:\n"), "{markdown}");
+
+        // Methods aren't in `paths`
+        let new = fixture_item(&doc, "new");
+        let markdown = new.to_markdown(&doc, &opts).unwrap();
+        assert!(markdown.contains("`new`:\n"), "{markdown}");
+    }
+
+    #[test]
+    fn test_precise_capturing() {
+        let iterator_bound = json!({
+            "trait_bound": {
+                "trait": {
+                    "name": "Iterator",
+                    "id": "2:8497:179",
+                    "args": {
+                        "angle_bracketed": {
+                            "args": [],
+                            "bindings": [{
+                                "name": "Item",
+                                "args": {
+                                    "angle_bracketed": {
+                                        "args": [],
+                                        "bindings": [],
+                                    },
+                                },
+                                "binding": {
+                                    "equality": {
+                                        "type": { "primitive": "u8" },
+                                    },
+                                },
+                            }],
+                        },
+                    },
+                },
+                "generic_params": [],
+                "modifier": "none",
+            },
+        });
+        let return_type = |use_bound| {
+            let impl_trait = json!({
+                "impl_trait": [iterator_bound.clone(), use_bound],
+            });
+            serde_json::from_value::<Type>(impl_trait)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            return_type(json!({ "use": ["'a", "T"] })),
+            "impl Iterator<Item = u8> + use<'a, T>"
+        );
+        assert_eq!(
+            return_type(json!({
+                "use": [{ "lifetime": "'a" }, { "param": "T" }],
+            })),
+            "impl Iterator<Item = u8> + use<'a, T>"
+        );
+    }
+
+    #[test]
+    fn test_hide_deprecated() {
+        let doc = fixtures_doc();
+        let sub = fixture_item(&doc, "sub");
+        assert!(sub.is_deprecated());
+        assert!(!fixture_item(&doc, "add").is_deprecated());
+
+        let markdown = sub.to_markdown(&doc, &PrintOptions::default()).unwrap();
+        let notice = "**Deprecated**: use `add` with a negative number instead";
+        assert!(markdown.contains(notice), "{markdown}");
+
+        let opts = PrintOptions {
+            hide_deprecated: true,
+            ..PrintOptions::default()
+        };
+        assert!(!doc.is_rendered(&sub.id, &opts));
+        assert!(doc.is_rendered(&sub.id, &PrintOptions::default()));
+    }
+
+    #[test]
+    fn test_non_exhaustive_notes() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            non_exhaustive_notes: true,
+            ..PrintOptions::default()
+        };
+        let note = "Note: this struct is `#[non_exhaustive]`";
+        let markdown =
+            |name| fixture_item(&doc, name).to_markdown(&doc, &opts).unwrap();
+        assert!(markdown("Config").contains(note));
+        assert!(!markdown("Wrapper").contains(note));
+
+        let default = fixture_item(&doc, "Config")
+            .to_markdown(&doc, &PrintOptions::default())
+            .unwrap();
+        assert!(!default.contains(note));
+    }
+
+    #[test]
+    fn test_asciidoc() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            format: OutputFormat::AsciiDoc,
+            ..PrintOptions::default()
+        };
+        let asciidoc =
+            |name| fixture_item(&doc, name).to_markdown(&doc, &opts).unwrap();
+        let expected = include_str!("../test-data/fixtures/double.adoc");
+        assert_eq!(asciidoc("double"), expected);
+        let expected = include_str!("../test-data/fixtures/wrapper.adoc");
+        assert_eq!(asciidoc("Wrapper"), expected);
+    }
+
+    #[test]
+    fn test_higher_ranked_where_predicates() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        assert_eq!(
+            signature("filter_lines"),
+            "fn filter_lines<T>(lines: &[String], keep: T) -> Vec<&str> \
+                where for<'a> T: Fn(&'a str) -> bool"
+        );
+        assert_eq!(
+            signature("count_matches"),
+            "fn count_matches<T>(lines: &[String], matches: T) -> usize \
+                where T: for<'a> Fn(&'a str) -> bool"
+        );
+    }
+
+    #[test]
+    fn test_enum_repr_and_discriminants() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Opcode")
+            .format_definition("Opcode", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "\
+#[repr(u16)]
+pub enum Opcode {
+    /// Does nothing.
+    Nop = 0,
+    /// Stops the machine.
+    Halt = 0x10,
+    /// Follows `Halt`.
+    Resume,
+}
+"
+        );
+
+        let shape = fixture_item(&doc, "Shape")
+            .format_definition("Shape", &doc, &PrintOptions::default())
+            .unwrap();
+        assert!(shape.starts_with("pub enum Shape {"));
+    }
+
+    #[test]
+    fn test_receivers() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Job")
+            .format_definition("Job", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "\
+pub trait Job {
+    fn name(&self) -> &str;
+    fn run(&mut self);
+    fn poll(self: Pin<&mut Self>) -> bool;
+    fn consume(self: Box<Self>) -> u32;
+    fn finish(self) where Self: Sized;
+}
+"
+        );
+        let consume = fixture_item(&doc, "consume");
+        let function = consume.inner.as_ref().unwrap().function.as_ref();
+        assert!(function.unwrap().decl.has_receiver());
+    }
+
+    #[test]
+    fn test_tuple_return_notes() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            tuple_return_notes: true,
+            ..PrintOptions::default()
+        };
+        let code_block = fixture_item(&doc, "parse_prefix")
+            .format_code_block("parse_prefix", None, &doc, &opts)
+            .unwrap();
+        assert_eq!(
+            code_block,
+            "```rust
+pub fn parse_prefix(input: &str) -> (usize, bool);
+// Returns:
+// - The number of digits at the start of `input`.
+// - Whether they're all of `input`.
+```
+"
+        );
+
+        let code_block = fixture_item(&doc, "parse_prefix")
+            .format_code_block(
+                "parse_prefix",
+                None,
+                &doc,
+                &PrintOptions::default(),
+            )
+            .unwrap();
+        assert!(!code_block.contains("// Returns:"));
+
+        let docs = "Intro.\n\n# Returns\n\nA count.\n\n```\n# hidden\n```\n\n# Panics\n\nNever.";
+        assert_eq!(
+            doc_section(docs, "Returns"),
+            Some("A count.\n\n```\n# hidden\n```")
+        );
+        assert_eq!(doc_section(docs, "Panics"), Some("Never."));
+        assert_eq!(doc_section(docs, "Errors"), None);
+    }
+
+    #[test]
+    fn test_object_safety() {
+        let doc = fixtures_doc();
+        let is_object_safe = |name| {
+            let item = fixture_item(&doc, name);
+            let trait_details = item.inner.as_ref().unwrap().trait_.as_ref();
+            trait_details.unwrap().is_object_safe(&doc)
+        };
+        // `finish` opts out with `where Self: Sized`
+        assert!(is_object_safe("Job"));
+        // An associated const
+        assert!(!is_object_safe("HasId"));
+        // A generic associated type
+        assert!(!is_object_safe("Container"));
+        // An async method
+        assert!(!is_object_safe("Fetch"));
+
+        assert!(mentions_self("Option<Self>"));
+        assert!(!mentions_self("Option<Self::Item<T>>"));
+        assert!(!mentions_self("SelfRef"));
+
+        let opts = PrintOptions {
+            object_safety_notes: true,
+            ..PrintOptions::default()
+        };
+        let note = "Not object-safe (cannot be used as dyn Trait)";
+        let markdown =
+            |name| fixture_item(&doc, name).to_markdown(&doc, &opts).unwrap();
+        assert!(markdown("Fetch").contains(note));
+        assert!(!markdown("Job").contains(note));
+    }
+
+    #[test]
+    fn test_implementors() {
+        let doc = fixtures_doc();
+        let has_id = fixture_item(&doc, "HasId");
+        let implementors = doc
+            .implementors(&has_id.id)
+            .into_iter()
+            .filter_map(RustDocItem::signature)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            implementors,
+            ["impl HasId for Shape", "impl HasId for i32"]
+        );
+        assert!(doc.implementors(&fixture_item(&doc, "add").id).is_empty());
+    }
+
+    #[test]
+    fn test_header() {
+        let doc = fixtures_doc();
+        assert_eq!(doc.crate_name(), "fixtures");
+        assert_eq!(
+            doc.format_header(&PrintOptions::default()),
+            "# Crate fixtures v0.1.0\n\nIncludes private items: true\n\n"
+        );
+        let opts = PrintOptions {
+            format: OutputFormat::AsciiDoc,
+            ..PrintOptions::default()
+        };
+        assert!(doc
+            .format_header(&opts)
+            .starts_with("= Crate fixtures v0.1.0\n"));
+    }
+
+    #[test]
+    fn test_nested_types() {
+        let doc = fixtures_doc();
+        let output = |name| {
+            let decl = fixture_fn(&doc, name);
+            decl.output.as_ref().unwrap().to_string()
+        };
+        assert_eq!(output("cube"), "[[[u8; 2]; 2]; 2]");
+        assert_eq!(output("nested_vecs"), "Vec<Vec<Vec<u8>>>");
+
+        // Well past the default depth, but within serde_json's recursion limit
+        let mut deep = json!({ "primitive": "u8" });
+        for _ in 0..100 {
+            deep = json!({ "slice": deep });
+        }
+        let deep = serde_json::from_value::<Type>(deep).unwrap();
+        let rendered = deep.to_string();
+        let depth = DEFAULT_MAX_TYPE_DEPTH;
+        let expected = format!("{}...{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(rendered, expected);
+
+        let _max_type_depth = MaxTypeDepth::set(2);
+        assert_eq!(output("nested_vecs"), "Vec<Vec<...>>");
+        let _truncation_marker = TruncationMarker::set("…".to_owned());
+        assert_eq!(output("nested_vecs"), "Vec<Vec<…>>");
+    }
+
+    #[test]
+    fn test_dyn_with_bindings() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        assert_eq!(
+            signature("byte_stream"),
+            "fn byte_stream() -> Box<dyn Iterator<Item = u8> + Send>"
+        );
+        assert_eq!(
+            signature("sum_stream"),
+            "fn sum_stream(stream: &mut (dyn Iterator<Item = u8> + Send)) -> u32"
+        );
+    }
+
+    #[test]
+    fn test_filter_map_items() {
+        let doc = fixtures_doc();
+        let inner = fixture_item(&doc, "inner").id.clone();
+        let root = doc.root.clone();
+        let in_inner = doc
+            .paths
+            .iter()
+            .filter(|(_, summary)| {
+                summary
+                    .path
+                    .starts_with(&["fixtures".to_owned(), "inner".to_owned()])
+            })
+            .map(|(id, _)| id.clone())
+            .collect::<HashSet<_>>();
+
+        let filtered = doc.filter_map_items(|item| {
+            let keep = item.id == root || in_inner.contains(&item.id);
+            keep.then_some(item)
+        });
+
+        let tree = filtered.module_tree();
+        assert_eq!(tree.name, "fixtures");
+        assert!(tree.item_ids.is_empty());
+        let module_names = tree
+            .modules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(module_names, ["inner"]);
+        assert!(!tree.modules[0].item_ids.is_empty());
+        assert_eq!(filtered.index.get(&inner).unwrap().name(), Some("inner"));
+
+        // Every local id the items refer to still exists
+        assert!(filtered
+            .paths
+            .keys()
+            .filter(|id| id.starts_with("0:"))
+            .all(|id| filtered.index.contains_key(id)));
+        let json = serde_json::to_value(&filtered.index).unwrap();
+        let mut referenced = Vec::new();
+        collect_local_ids(&json, &mut referenced);
+        for id in referenced {
+            assert!(filtered.index.contains_key(&id), "Dangling id {id}");
+        }
+    }
+
+    /// Collects the local ids, e.g. `0:12:345`, among the strings in `json`.
+    fn collect_local_ids(json: &Value, ids: &mut Vec<String>) {
+        match json {
+            Value::String(s) if s.starts_with("0:") => ids.push(s.clone()),
+            Value::Array(values) => values
+                .iter()
+                .for_each(|value| collect_local_ids(value, ids)),
+            Value::Object(map) =>
+                map.values().for_each(|value| collect_local_ids(value, ids)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_pattern_params() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        // rustdoc prints tuple struct patterns as just their path and drops
+        // the trailing comma of 1-tuple patterns; only the types are exact
+        assert_eq!(
+            signature("distance"),
+            "fn distance((x, y): (i32, i32), Meters: Meters) -> u32"
+        );
+        assert_eq!(signature("first_of"), "fn first_of((first): (u8,)) -> u8");
+    }
+
+    #[test]
+    fn test_signatures() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature();
+        let cases = [
+            ("add", "fn add(a: u32, b: u32) -> u32"),
+            ("Ref", "struct Ref<'a, 'b: 'a, T: 'a + ?Sized, const N: usize, U = u8>"),
+            ("Marker", "struct Marker"),
+            ("Either", "enum Either<L, R> where L: Clone"),
+            ("Shape", "enum Shape"),
+            ("Job", "trait Job"),
+            ("Task", "trait Task<T> = Iterator<Item = T> + Send"),
+            ("Pairs", "type Pairs<T> where T: Clone = Vec<(T, T)>"),
+            ("SIZE", "const SIZE: usize"),
+            ("CALLS", "static mut CALLS: u32"),
+            ("inner", "mod inner"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(signature(name).as_deref(), Some(expected), "{name}");
+        }
+        assert_eq!(signature("twice"), None);
+
+        let markdown = fixture_item(&doc, "Either")
+            .format_definition("Either", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            markdown,
+            "pub enum Either<L, R> where L: Clone {\n    Left(L),\n    Right(R),\n}\n"
+        );
+        let markdown = fixture_item(&doc, "SIZE")
+            .format_definition("SIZE", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(markdown, "pub const SIZE: usize = 16;\n");
+    }
+
+    #[test]
+    fn test_bounded_assoc_type() {
+        let doc = fixtures_doc();
+        let markdown = fixture_item(&doc, "Spawn")
+            .format_definition("Spawn", &doc, &PrintOptions::default())
+            .unwrap();
+        let expected = "pub trait Spawn {\n    \
+            type Fut: std::future::Future<Output = ()> + Send;\n    \
+            fn spawn(&self) -> Self::Fut;\n}\n";
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn test_prelude() {
+        let doc = fixtures_doc();
+        let prelude = doc.format_prelude(&PrintOptions::default()).unwrap();
+        let expected = "Prelude\n-------\n\n\
+            `use fixtures::prelude::*;` brings these into scope:\n\n\
+            ```rust\n\
+            pub use crate::add;\n\
+            pub use crate::Shape as Figure;\n\
+            pub use crate::inner::*;\n\
+            ```\n";
+        assert_eq!(prelude, expected);
+
+        let opts = PrintOptions {
+            prelude_module: Some("inner".to_owned()),
+            ..PrintOptions::default()
+        };
+        assert_eq!(doc.format_prelude(&opts), None);
+        let opts = PrintOptions {
+            prelude_module: None,
+            ..PrintOptions::default()
+        };
+        assert_eq!(doc.format_prelude(&opts), None);
+    }
+
+    #[test]
+    fn test_write_markdown_with_progress() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+        let mut progress = Vec::new();
+        let mut out = Vec::new();
+        doc.write_markdown_with_progress(&mut out, &opts, |done, total| {
+            progress.push((done, total))
+        })
+        .unwrap();
+        let markdown = String::from_utf8(out).unwrap();
+
+        let total = progress.len();
+        assert!(total > 0);
+        assert!(progress
+            .iter()
+            .enumerate()
+            .all(|(i, &p)| p == (i + 1, total)));
+        // Items are in source order, with `extern` blocks at the end
+        let add = markdown.find("`add`:").unwrap();
+        let double = markdown.find("`double`:").unwrap();
+        let extern_blocks = markdown.find("Extern Blocks").unwrap();
+        let printf = markdown.find("`printf`:").unwrap();
+        assert!(add < double && double < extern_blocks);
+        assert!(extern_blocks < printf);
+
+        let mut again = Vec::new();
+        doc.write_markdown(&mut again, &opts).unwrap();
+        assert_eq!(String::from_utf8(again).unwrap(), markdown);
+    }
+
+    #[test]
+    fn test_fallibility_notes() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            fallibility_notes: true,
+            ..PrintOptions::default()
+        };
+        let code_block = |name| {
+            fixture_item(&doc, name)
+                .format_code_block(name, None, &doc, &opts)
+                .unwrap()
+        };
+        assert_eq!(
+            code_block("parse_level"),
+            "```rust
+pub fn parse_level(s: &str) -> Result<Level, String>;
+// Returns Err on failure: If `s` isn't the name of a level, ignoring case.
+```
+"
+        );
+        assert_eq!(
+            code_block("find_byte"),
+            "```rust
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize>;
+// Returns None when absent: The index of `needle`, or `None` if it isn't in `haystack`.
+```
+"
+        );
+        assert_eq!(
+            code_block("last_byte"),
+            "```rust
+pub fn last_byte(bytes: &[u8]) -> Option<&u8>;
+// Returns None when absent.
+```
+"
+        );
+        assert!(!code_block("add").contains("//"));
+
+        let code_block = fixture_item(&doc, "parse_level")
+            .format_code_block(
+                "parse_level",
+                None,
+                &doc,
+                &PrintOptions::default(),
+            )
+            .unwrap();
+        assert!(!code_block.contains("Returns Err"));
+    }
+
+    #[test]
+    fn test_sealed_traits() {
+        let doc = fixtures_doc();
+        let is_sealed = |name| {
+            let item = fixture_item(&doc, name);
+            let trait_details = item.inner.as_ref().unwrap().trait_.as_ref();
+            trait_details.unwrap().is_sealed(&doc)
+        };
+        assert!(is_sealed("ColorSpace"));
+        assert!(!is_sealed("Job"));
+        assert!(!is_sealed("Sealed"));
+
+        assert!(doc.is_publicly_reachable(&fixture_item(&doc, "mul").id));
+        assert!(!doc.is_publicly_reachable(&fixture_item(&doc, "Sealed").id));
+
+        let opts = PrintOptions {
+            sealed_notes: true,
+            ..PrintOptions::default()
+        };
+        let note = "Sealed (cannot be implemented outside this crate)";
+        let markdown = fixture_item(&doc, "ColorSpace")
+            .to_markdown(&doc, &opts)
+            .unwrap();
+        assert!(markdown.contains(note), "{markdown}");
+        let markdown = fixture_item(&doc, "ColorSpace")
+            .to_markdown(&doc, &PrintOptions::default())
+            .unwrap();
+        assert!(!markdown.contains(note));
+    }
+
+    #[test]
+    fn test_qualified_assoc_items() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            qualified_assoc_items: true,
+            ..PrintOptions::default()
+        };
+        let headings = doc
+            .index
+            .values()
+            .filter(|item| item.name.as_deref() == Some("new"))
+            .filter_map(|item| item.to_markdown(&doc, &opts))
+            .filter_map(|markdown| {
+                let heading = markdown.lines().find(|line| line.ends_with(':'));
+                heading.map(str::to_owned)
+            })
+            .collect::<HashSet<_>>();
+        let expected = ["`Wrapper::new`:", "`Celsius::new`:"]
+            .map(str::to_owned)
+            .into();
+        assert_eq!(headings, expected);
+
+        // Trait items are qualified by their trait
+        let run = doc.index.values().find(|item| {
+            item.name.as_deref() == Some("run")
+                && doc.assoc_item_owner(&item.id) == Some("Job")
+        });
+        assert!(run.is_some());
+
+        let new = fixture_item(&doc, "new");
+        let markdown = new.to_markdown(&doc, &PrintOptions::default()).unwrap();
+        assert!(markdown.contains("`new`:\n"), "{markdown}");
+    }
+
+    #[test]
+    fn test_impl_flags() {
+        let doc = fixtures_doc();
+        let impls = |item: &RustDocItem| {
+            let inner = item.inner.as_ref().unwrap();
+            inner.struct_.as_ref().unwrap().impls.clone()
+        };
+        let impl_details = |id: &String| {
+            let item = &doc.index[id];
+            let inner = item.inner.as_ref().unwrap();
+            inner.impl_.as_ref().unwrap()
+        };
+        let signatures = |filter: fn(&ImplDetails) -> bool| {
+            impls(fixture_item(&doc, "Marker"))
+                .iter()
+                .filter(|id| filter(impl_details(id)))
+                .map(|id| doc.index[id].signature().unwrap())
+                .collect::<HashSet<_>>()
+        };
+        let synthetic = signatures(ImplDetails::is_synthetic);
+        assert!(synthetic.contains("impl Send for Marker"), "{synthetic:?}");
+        let blanket = signatures(ImplDetails::is_blanket);
+        assert!(blanket.contains("impl<T> From<T> for T"), "{blanket:?}");
+        assert!(synthetic.is_disjoint(&blanket));
+    }
+
+    #[test]
+    fn test_auto_trait_impls() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            auto_trait_impls: true,
+            ..PrintOptions::default()
+        };
+        let markdown = |name, opts| {
+            fixture_item(&doc, name).to_markdown(&doc, opts).unwrap()
+        };
+        let expected = "Auto Trait Implementations: RefUnwindSafe, Send, \
+                        Sync, Unpin, UnwindSafe\n";
+        assert!(markdown("Marker", &opts).contains(expected));
+        let expected = "Auto Trait Implementations: RefUnwindSafe, !Send, \
+                        !Sync, Unpin, UnwindSafe\n";
+        assert!(markdown("Handle", &opts).contains(expected));
+        let markdown = markdown("Marker", &PrintOptions::default());
+        assert!(!markdown.contains("Auto Trait Implementations"));
+    }
+
+    #[test]
+    fn test_strict() {
+        let doc = fixtures_doc();
+        let strict = PrintOptions {
+            strict: true,
+            ..PrintOptions::default()
+        };
+        let twice = fixture_item(&doc, "twice");
+        // Without strict, the macro is printed without its definition
+        let markdown = twice
+            .try_to_markdown(&doc, &PrintOptions::default())
+            .unwrap()
+            .unwrap();
+        assert!(!markdown.contains("```"), "{markdown}");
+        assert_eq!(
+            twice.try_to_markdown(&doc, &strict),
+            Err(RenderError::UnsupportedKind {
+                id: twice.id.clone(),
+                kind: "macro".to_owned(),
+            })
+        );
+        let add = fixture_item(&doc, "add");
+        assert!(add.try_to_markdown(&doc, &strict).unwrap().is_some());
+
+        let err = doc.write_markdown(&mut io::sink(), &strict).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("`macro`"), "{err}");
+        doc.write_markdown(&mut io::sink(), &PrintOptions::default())
+            .unwrap();
+
+        // Without the macro, strict rendering succeeds
+        let doc = doc.filter_map_items(|item| {
+            let is_macro = item.kind() == Some(RustDocItemKind::Macro);
+            (!is_macro).then_some(item)
+        });
+        doc.write_markdown(&mut io::sink(), &strict).unwrap();
+    }
+
+    #[test]
+    fn test_unnamed_params() {
+        let doc = fixtures_doc();
+        let definition = |opts: &PrintOptions| {
+            fixture_item(&doc, "Visitor")
+                .format_definition("Visitor", &doc, opts)
+                .unwrap()
+        };
+        let expected = "pub trait Visitor {\n    \
+            fn visit(&mut self, _: &str, _: usize);\n}\n";
+        assert_eq!(definition(&PrintOptions::default()), expected);
+
+        let opts = PrintOptions {
+            positional_param_names: true,
+            ..PrintOptions::default()
+        };
+        let expected = "pub trait Visitor {\n    \
+            fn visit(&mut self, arg1: &str, arg2: usize);\n}\n";
+        assert_eq!(definition(&opts), expected);
+
+        // Named params keep their names
+        let markdown = fixture_item(&doc, "add")
+            .format_definition("add", &doc, &opts)
+            .unwrap();
+        assert_eq!(markdown, "pub fn add(a: u32, b: u32) -> u32;\n");
+
+        let visit = fixture_fn(&doc, "visit");
+        // Empty names are written as `_` too
+        let mut json = serde_json::to_value(visit).unwrap();
+        json["inputs"][1][0] = json!("");
+        let visit = serde_json::from_value::<FunctionDecl>(json).unwrap();
+        assert_eq!(
+            visit.format("visit", false, None),
+            "fn visit(&mut self, _: &str, _: usize)"
+        );
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let doc = RustDoc::from_slice(FIXTURES_JSON_BYTES).unwrap();
+        let expected = fixtures_doc();
+        assert_eq!(doc.index.len(), expected.index.len());
+        assert_eq!(
+            fixture_item(&doc, "add").signature(),
+            fixture_item(&expected, "add").signature()
+        );
+        assert!(RustDoc::from_slice(b"{").is_err());
+    }
+
+    #[test]
+    fn test_composite_generic_args() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        assert_eq!(
+            signature("filter_all"),
+            "fn filter_all(items: Vec<u32>, filters: Vec<fn(u32) -> bool>) \
+             -> Vec<u32>"
+        );
+        assert_eq!(
+            signature("cell_names"),
+            "fn cell_names(names: std::collections::HashMap<(u8, u8), String>) \
+             -> Vec<((u8, u8), String)>"
+        );
+    }
+
+    #[test]
+    fn test_type_index() {
+        let doc = fixtures_doc();
+        let index = doc.type_index(&PrintOptions::default());
+        assert!(index.starts_with("Types\n-----\n\n"), "{index}");
+        assert!(
+            index.contains(
+                "- [`Marker`](#marker) (struct): A marker with no fields.\n"
+            ),
+            "{index}"
+        );
+        assert!(index.contains(
+            "- [`Task`](#task) (trait_alias): An iterator of `T`s which can \
+             be sent between threads.\n"
+        ), "{index}");
+        // Fns, private traits, and modules aren't types
+        assert!(!index.contains("`add`"));
+        assert!(!index.contains("Sealed"));
+        assert!(!index.contains("(module)"));
+        // Sorted by path
+        let celsius = index.find("`Celsius`").unwrap();
+        let wrapper = index.find("`Wrapper`").unwrap();
+        assert!(celsius < wrapper);
+
+        let opts = PrintOptions {
+            format: OutputFormat::AsciiDoc,
+            ..PrintOptions::default()
+        };
+        let index = doc.type_index(&opts);
+        assert!(index.contains(
+            "* <<marker,`Marker`>> (struct): A marker with no fields.\n"
+        ));
+    }
+
+    #[test]
+    fn test_self_sized_notes() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            object_safety_notes: true,
+            ..PrintOptions::default()
+        };
+        let definition = fixture_item(&doc, "Job")
+            .format_definition("Job", &doc, &opts)
+            .unwrap();
+        assert!(
+            definition.contains(
+                "    fn finish(self) where Self: Sized; // requires Self: \
+                 Sized, so can't be called on dyn Trait\n"
+            ),
+            "{definition}"
+        );
+        assert!(definition.contains("    fn run(&mut self);\n"));
+    }
+
+    #[test]
+    fn test_primitive_types() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        assert_eq!(
+            signature("primitives"),
+            "fn primitives(a: bool, b: char, c: &str, d: i8, e: i16, f: i32, \
+             g: i64, h: i128, i: isize, j: u8, k: u16, l: u32, m: u64, \
+             n: u128, o: usize, p: f32, q: f64, r: (), s: [u8; 2], \
+             t: &[u16], u: *const u32, v: fn()) -> !"
+        );
+        // rustdoc drops an explicit `-> ()`
+        assert_eq!(signature("unit"), "fn unit()");
+        assert_eq!(signature("boxed_str"), "fn boxed_str() -> Box<str>");
+        assert_eq!(
+            signature("char_flag"),
+            "fn char_flag() -> Option<(char, bool)>"
+        );
+
+        let never = json!({ "primitive": "never" });
+        let never = serde_json::from_value::<Type>(never);
+        assert_eq!(never.unwrap().to_string(), "!");
+        let unit = serde_json::from_value::<Type>(json!({ "tuple": [] }));
+        assert_eq!(unit.unwrap().to_string(), "()");
+    }
+
+    #[test]
+    fn test_doc_heading_offset() {
+        let doc = fixtures_doc();
+        let clamp_to = fixture_item(&doc, "clamp_to");
+        let docs = |opts: &PrintOptions| {
+            clamp_to.format_docs(clamp_to.docs().unwrap(), &doc, opts)
+        };
+
+        let expected = "\
+Clamps `value` to at most `max`.
+
+#### Examples
+
+```
+# use fixtures::clamp_to;
+assert_eq!(clamp_to(7, 5), 5);
+```
+
+##### Details
+
+Equivalent to `value.min(max)`.";
+        assert_eq!(docs(&PrintOptions::default()), expected);
+
+        let opts = PrintOptions {
+            doc_heading_offset: 5,
+            ..PrintOptions::default()
+        };
+        let shifted = docs(&opts);
+        assert!(shifted.contains("\n###### Examples\n"), "{shifted}");
+        assert!(shifted.contains("\n###### Details\n"), "{shifted}");
+
+        let opts = PrintOptions {
+            format: OutputFormat::AsciiDoc,
+            ..PrintOptions::default()
+        };
+        assert!(docs(&opts).contains("\n# Examples\n"));
+    }
+
+    #[test]
+    fn test_unnamed_resolved_paths() {
+        // rustdoc sometimes leaves a path's name empty and relies on its id,
+        // so blank out `Meters` in `distance`'s params to simulate that
+        let distance_with = |id: Option<&str>| {
+            let mut json =
+                serde_json::from_str::<Value>(FIXTURES_JSON_STR).unwrap();
+            let distance = json["index"]
+                .as_object_mut()
+                .unwrap()
+                .values_mut()
+                .find(|item| item["name"] == "distance")
+                .unwrap();
+            let meters = &mut distance["inner"]["function"]["decl"]["inputs"]
+                [1][1]["resolved_path"];
+            meters["name"] = json!("");
+            if let Some(id) = id {
+                meters["id"] = json!(id);
+            }
+            let doc = RustDoc::try_from(json).unwrap();
+            fixture_item(&doc, "distance").signature().unwrap()
+        };
+        assert_eq!(
+            distance_with(None),
+            "fn distance((x, y): (i32, i32), Meters: Meters) -> u32"
+        );
+        // An id which resolves to nothing falls back to `_`
+        assert_eq!(
+            distance_with(Some("0:missing")),
+            "fn distance((x, y): (i32, i32), Meters: _) -> u32"
+        );
+    }
+
+    #[test]
+    fn test_explicit_future_returns() {
+        let doc = fixtures_doc();
+        let signature = fixture_item(&doc, "download").signature().unwrap();
+        assert_eq!(
+            signature,
+            "fn download(url: String) -> impl std::future::Future<Output = \
+                Result<Bytes, std::io::Error>> + Send"
+        );
+    }
+
+    #[test]
+    fn test_reachable_from() {
+        let doc = fixtures_doc();
+        let reachable_names = |path| {
+            doc.reachable_from(path)
+                .iter()
+                .filter_map(|id| doc.item(id)?.name())
+                .collect::<Vec<_>>()
+        };
+        // Breadth-first, so `Stop` (a param) precedes `Route` (the return
+        // type), and `Route`'s reference to itself doesn't loop forever
+        assert_eq!(
+            reachable_names("fixtures::plan_route"),
+            ["plan_route", "Stop", "Route", "StopKind", "Platform"]
+        );
+        assert_eq!(reachable_names("fixtures::Platform"), ["Platform"]);
+        assert!(reachable_names("fixtures::nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_mixed_variant_shapes() {
+        let doc = fixtures_doc();
+        let definition = fixture_item(&doc, "Frame")
+            .format_definition("Frame", &doc, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(
+            definition,
+            "\
+#[repr(u8)]
+pub enum Frame {
+    Ping = 1,
+    Data(u8),
+    Close { code: u32 },
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let doc = fixtures_doc();
+        let stats = doc.stats();
+        let mut paths = stats.keys().map(String::as_str).collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "fixtures",
+                "fixtures::inner",
+                "fixtures::prelude",
+                "fixtures::prim_i32",
+                "fixtures::private",
+            ]
+        );
+
+        let inner = &stats["fixtures::inner"];
+        let function = RustDocItemKind::Function;
+        assert_eq!(inner.kinds, HashMap::from([(function, 1)]));
+        assert_eq!(inner.total_undocumented(), 0);
+
+        let private = &stats["fixtures::private"];
+        let trait_ = RustDocItemKind::Trait;
+        assert_eq!(private.undocumented, HashMap::from([(trait_, 1)]));
+
+        // Child modules count towards their parent
+        let root = &stats["fixtures"];
+        assert_eq!(root.kinds[&RustDocItemKind::Module], 4);
+        assert!(root.total() > root.total_undocumented());
+    }
+
+    #[test]
+    fn test_generic_and_reference_impl_targets() {
+        let doc = fixtures_doc();
+        let markdown = |name| {
+            fixture_item(&doc, name)
+                .to_markdown(&doc, &PrintOptions::default())
+                .unwrap()
+        };
+        let labeled = markdown("Labeled");
+        let impl_line =
+            "impl<T: std::fmt::Display> Display for Labeled<T> { ... }\n";
+        assert!(labeled.contains(impl_line), "{labeled}");
+        let gauge = markdown("Gauge");
+        let impl_line = "impl Display for &Gauge { ... }\n";
+        assert!(gauge.contains(impl_line), "{gauge}");
+    }
+
+    #[test]
+    fn test_front_matter() {
+        use std::time::Duration;
+
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(format_timestamp(leap_day), "2024-02-29T12:34:56Z");
+        let new_year = UNIX_EPOCH + Duration::from_secs(946_684_799);
+        assert_eq!(format_timestamp(new_year), "1999-12-31T23:59:59Z");
+
+        let doc = fixtures_doc();
+        assert_eq!(
+            doc.format_front_matter(leap_day),
+            "---\ntitle: \"fixtures\"\ncrate_version: \"0.1.0\"\n\
+             generated: 2024-02-29T12:34:56Z\n---\n\n"
+        );
+
+        let opts = PrintOptions {
+            front_matter: true,
+            ..Default::default()
+        };
+        let mut markdown = Vec::new();
+        doc.write_markdown(&mut markdown, &opts).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+        // Only once, at the very top
+        assert!(markdown.starts_with("---\ntitle: \"fixtures\"\n"));
+        assert_eq!(markdown.matches("\ntitle: ").count(), 1);
+    }
+
+    #[test]
+    fn test_inherited_methods() {
+        let doc = fixtures_doc();
+        let definition = |opts: &PrintOptions| {
+            fixture_item(&doc, "LoudGreeter")
+                .format_definition("LoudGreeter", &doc, opts)
+                .unwrap()
+        };
+        let opts = PrintOptions {
+            inherited_methods: true,
+            ..Default::default()
+        };
+        // `Send` isn't local, so it can't be expanded
+        assert_eq!(
+            definition(&opts),
+            "\
+pub trait LoudGreeter: Greeter + Send {
+    fn shout(&self) -> String;
+    // From `Greeter`:
+    // fn greet(&self) -> String;
+    // From `Named`:
+    // fn name(&self) -> String;
+}
+"
+        );
+        let default = definition(&PrintOptions::default());
+        assert!(!default.contains("From `Greeter`"), "{default}");
+    }
+
+    #[test]
+    fn test_max_const_value_chars() {
+        let doc = fixtures_doc();
+        let definition = |name, opts: &PrintOptions| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, opts)
+                .unwrap()
+        };
+        let opts = PrintOptions::default();
+        assert_eq!(
+            definition("LICENSE_NOTICE", &opts),
+            "pub const LICENSE_NOTICE: &str = \"...\";\n"
+        );
+        // rustdoc only records statics' literal values, but the type is
+        // still printed in full
+        assert_eq!(
+            definition("SQUARES", &opts),
+            "pub static SQUARES: [u16; 32] = _;\n"
+        );
+        let unlimited = PrintOptions {
+            max_const_value_chars: None,
+            ..Default::default()
+        };
+        let notice = definition("LICENSE_NOTICE", &unlimited);
+        assert!(notice.ends_with("documentation files\";\n"), "{notice}");
+
+        let opts = PrintOptions {
+            max_const_value_chars: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(format_const_value("[1, 2, 3]", &opts), "[...]");
+        assert_eq!(format_const_value("&[1, 2]", &opts), "&[...]");
+        assert_eq!(format_const_value("1 << 20", &opts), "...");
+        assert_eq!(format_const_value("b\"\\x00\\x01\"", &opts), "b\"...\"");
+        assert_eq!(format_const_value("16", &opts), "16");
+    }
+
+    #[test]
+    fn test_module_path_of() {
+        let doc = fixtures_doc();
+        let module_path =
+            |name| doc.module_path_of(fixture_item(&doc, name).id());
+        assert_eq!(module_path("mul").as_deref(), Some("fixtures::inner"));
+        assert_eq!(module_path("add").as_deref(), Some("fixtures"));
+        assert_eq!(module_path("inner").as_deref(), Some("fixtures"));
+        // The crate root has no containing module
+        assert_eq!(doc.module_path_of(&doc.root), None);
+        // Methods aren't in `paths`
+        assert_eq!(module_path("map"), None);
+        assert_eq!(doc.module_path_of("0:missing"), None);
+    }
+
+    #[test]
+    fn test_multiline_where_clauses() {
+        let doc = fixtures_doc();
+        let definition = |name, opts: &PrintOptions| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, opts)
+                .unwrap()
+        };
+        let opts = PrintOptions::default();
+        assert_eq!(
+            definition("merge_by", &opts),
+            "\
+pub fn merge_by<K, V, F>(into: &mut Vec<V>, items: Vec<V>, key: F)
+where
+    K: Ord,
+    V: Clone + Send,
+    F: Fn(&V) -> K;
+"
+        );
+        assert_eq!(
+            definition("Cache", &opts),
+            "\
+pub struct Cache<K, V>
+where
+    K: std::hash::Hash + Eq,
+    V: Clone,
+{
+    pub entries: Vec<(K, V)>,
+}
+"
+        );
+        // A single predicate stays inline
+        assert_eq!(
+            definition("Either", &opts).lines().next(),
+            Some("pub enum Either<L, R> where L: Clone {")
+        );
+        // As do signatures, and everything with a higher threshold
+        let signature = fixture_item(&doc, "merge_by").signature().unwrap();
+        assert!(signature
+            .ends_with(") where K: Ord, V: Clone + Send, F: Fn(&V) -> K"));
+        let inline = PrintOptions {
+            multiline_where_predicates: Some(4),
+            ..Default::default()
+        };
+        let cache = definition("Cache", &inline);
+        assert!(
+            cache.starts_with("pub struct Cache<K, V> where K: "),
+            "{cache}"
+        );
+    }
+
+    #[test]
+    fn test_html_output() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            format: OutputFormat::Html,
+            ..Default::default()
+        };
+        let mut html = Vec::new();
+        doc.write_markdown(&mut html, &opts).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n"));
+        assert!(html.ends_with("</main>\n</body>\n</html>\n"));
+
+        // Every tag is one we emit and is closed in order, so no unescaped
+        // `<` from a type or doc comment slipped through
+        const VOID_TAGS: [&str; 2] = ["meta", "hr"];
+        const TAGS: [&str; 18] = [
+            "html", "head", "title", "style", "body", "nav", "main", "ul",
+            "li", "a", "h1", "h2", "h3", "p", "pre", "code", "meta", "hr",
+        ];
+        let mut open_tags = Vec::new();
+        let body = html.strip_prefix("<!DOCTYPE html>").unwrap();
+        for tag in body.split('<').skip(1) {
+            let (tag, text) = tag.split_once('>').unwrap();
+            assert!(!text.contains('>'), "unescaped `>` in {text:?}");
+            let name = tag.split(' ').next().unwrap();
+            match name.strip_prefix('/') {
+                Some(name) => assert_eq!(open_tags.pop(), Some(name)),
+                None if VOID_TAGS.contains(&name) => {}
+                None => {
+                    assert!(TAGS.contains(&name), "unexpected tag <{tag}>");
+                    open_tags.push(name);
+                }
+            }
+        }
+        assert!(open_tags.is_empty(), "{open_tags:?}");
+
+        // Types and docs are escaped
+        assert!(html.contains("Wrapper&lt;U&gt;"));
+        assert!(html.contains("bytes: &amp;[u8]"));
+        // The sidebar links to each item
+        assert!(html.contains("<li><a href=\"#wrapper\">Wrapper</a></li>"));
+        assert_eq!(escape_html("<a & 'b'>"), "&lt;a &amp; &#39;b&#39;&gt;");
+    }
+
+    #[test]
+    fn test_unknown_types() {
+        // A nightly-only `dyn* Display`, which this crate doesn't support
+        let dyn_star = json!({
+            "dyn_star": { "traits": [], "lifetime": null },
+        });
+        let type_ = serde_json::from_value::<Type>(dyn_star.clone());
+        assert_eq!(
+            type_.unwrap().to_string(),
+            "/* unsupported type: dyn_star */"
+        );
+        let slice = serde_json::from_value::<Type>(json!({
+            "slice": dyn_star.clone(),
+        }));
+        assert_eq!(
+            slice.unwrap().to_string(),
+            "[/* unsupported type: dyn_star */]"
+        );
+        let infer = serde_json::from_value::<Type>(json!("infer"));
+        assert_eq!(infer.unwrap().to_string(), "_");
+
+        // The rest of the crate still parses and renders
+        let mut json =
+            serde_json::from_str::<Value>(FIXTURES_JSON_STR).unwrap();
+        let add = json["index"]
+            .as_object_mut()
+            .unwrap()
+            .values_mut()
+            .find(|item| item["name"] == "add")
+            .unwrap();
+        add["inner"]["function"]["decl"]["inputs"][1][1] = dyn_star;
+        let doc = RustDoc::try_from(json).unwrap();
+        assert_eq!(
+            fixture_item(&doc, "add").signature().unwrap(),
+            "fn add(a: u32, b: /* unsupported type: dyn_star */) -> u32"
+        );
+        assert!(fixture_item(&doc, "Wrapper").signature().is_some());
+    }
+
+    #[test]
+    fn test_ufcs_methods() {
+        let doc = fixtures_doc();
+        let markdown = |name, opts: &PrintOptions| {
+            fixture_item(&doc, name).to_markdown(&doc, opts).unwrap()
+        };
+        let sugared = markdown("Tally", &PrintOptions::default());
+        assert!(
+            sugared.contains("    pub fn push(&mut self, x: u32) -> u32;\n"),
+            "{sugared}"
+        );
+        assert!(
+            sugared.contains("    pub fn zero() -> Self;\n"),
+            "{sugared}"
+        );
+
+        let opts = PrintOptions {
+            ufcs_methods: true,
+            ..Default::default()
+        };
+        let ufcs = markdown("Tally", &opts);
+        let add =
+            "    pub fn Tally::push(self: &mut Tally<u32>, x: u32) -> u32;\n";
+        assert!(ufcs.contains(add), "{ufcs}");
+        assert!(
+            ufcs.contains("    pub fn Tally::zero() -> Self;\n"),
+            "{ufcs}"
+        );
+
+        // Trait methods keep `Self`, as the implementor isn't known
+        let named = markdown("Named", &opts);
+        let name = "    fn Named::name(self: &Self) -> String;\n";
+        assert!(named.contains(name), "{named}");
+    }
+
+    #[test]
+    fn test_const_generic_trait_args() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+        let definition = |name| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, &opts)
+                .unwrap()
+        };
+        assert_eq!(
+            definition("read_chunk"),
+            "pub fn read_chunk<T: Chunked<4>>(data: &T) -> [u8; 4];\n"
+        );
+        assert_eq!(
+            definition("read_wide_chunk"),
+            "pub fn read_wide_chunk<T>(data: &T) -> [u8; 8] where T: Chunked<8>;\n"
+        );
+        assert_eq!(
+            definition("chunker"),
+            "pub fn chunker() -> impl Chunked<4>;\n"
+        );
+        assert_eq!(
+            definition("boxed_chunker"),
+            "pub fn boxed_chunker() -> Box<dyn Chunked<4>>;\n"
+        );
+        assert_eq!(
+            definition("Chunked"),
+            "pub trait Chunked<const N: usize> {\n    \
+             fn first_chunk(&self) -> [u8; N];\n}\n"
+        );
+        let block = fixture_item(&doc, "Block")
+            .to_markdown(&doc, &opts)
+            .unwrap();
+        assert!(block.contains("impl Chunked<4> for Block"), "{block}");
+    }
+
+    #[test]
+    fn test_single_element_tuples() {
+        // Params and outputs share one type model, so both keep the comma
+        let one = serde_json::from_value::<Type>(json!({
+            "tuple": [{ "primitive": "u8" }],
+        }));
+        assert_eq!(one.unwrap().to_string(), "(u8,)");
+        let decl = serde_json::from_value::<FunctionDecl>(json!({
+            "inputs": [["pair", { "tuple": [{ "generic": "T" }] }]],
+            "output": { "tuple": [{ "generic": "T" }] },
+            "c_variadic": false,
+        }));
+        assert_eq!(
+            decl.unwrap().format("swap", false, None),
+            "fn swap(pair: (T,)) -> (T,)"
+        );
+    }
+
+    #[test]
+    fn test_changed_signatures() {
+        let old = fixtures_doc();
+        // The same crate, with `add`'s second param widened to a `u64`
+        let mut json =
+            serde_json::from_str::<Value>(FIXTURES_JSON_STR).unwrap();
+        let add = json["index"]
+            .as_object_mut()
+            .unwrap()
+            .values_mut()
+            .find(|item| item["name"] == "add")
+            .unwrap();
+        add["inner"]["function"]["decl"]["inputs"][1][1] =
+            json!({ "primitive": "u64" });
+        let new = RustDoc::try_from(json).unwrap();
+
+        assert_eq!(
+            new.changed_signatures(&old),
+            [ChangedSignature {
+                path: "fixtures::add".to_owned(),
+                kind: RustDocItemKind::Function,
+                before: "fn add(a: u32, b: u32) -> u32".to_owned(),
+                after: "fn add(a: u32, b: u64) -> u32".to_owned(),
+            }]
+        );
+        assert_eq!(
+            new.format_changed_signatures(&old, &PrintOptions::default()),
+            "\
+Changed Signatures
+------------------
+
+`fixtures::add`:
+
+Before:
+
+```rust
+fn add(a: u32, b: u32) -> u32
+```
+
+After:
+
+```rust
+fn add(a: u32, b: u64) -> u32
+```
+
+"
+        );
+        assert!(old.changed_signatures(&fixtures_doc()).is_empty());
+        let unchanged =
+            old.format_changed_signatures(&old, &Default::default());
+        assert_eq!(unchanged, "");
+    }
+
+    #[test]
+    fn test_struct_field_docs() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+        let definition = |name| {
+            fixture_item(&doc, name)
+                .format_definition(name, &doc, &opts)
+                .unwrap()
+        };
+        // The fixtures include private items, so no fields are stripped
+        assert_eq!(
+            definition("Account"),
+            "\
+pub struct Account {
+    /// Who holds the account.
+    ///
+    /// Joint accounts list the primary holder.
+    pub holder: String,
+    pub overdraft: bool,
+    balance: u64,
+}
+"
+        );
+        assert_eq!(definition("Millis"), "pub struct Millis(pub u64);\n");
+
+        // Inlined field docs aren't printed again under their own heading
+        let holder = fixture_item(&doc, "holder");
+        assert_eq!(holder.to_markdown(&doc, &opts), None);
+        let rendered = doc.render();
+        assert!(!rendered.contains("`holder`:"), "{rendered}");
+    }
+
+    #[test]
+    fn test_impls_under_their_type() {
+        let doc = fixtures_doc();
+        let account = fixture_item(&doc, "Account")
+            .to_markdown(&doc, &PrintOptions::default())
+            .unwrap();
+        // The receiver keeps its lifetime rather than becoming `self: &'a Self`
+        let inherent = "\
+impl Account {
+    pub fn holder_name<'a>(&'a self) -> &'a str;
+    fn covers(&self, amount: u64) -> bool;
+}
+";
+        assert!(account.contains(inherent), "{account}");
+        assert!(account.contains("impl Clone for Account"), "{account}");
+    }
+
+    #[test]
+    fn test_public_only() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            public_only: true,
+            ..PrintOptions::default()
+        };
+        let markdown = |name, opts: &PrintOptions| {
+            fixture_item(&doc, name).to_markdown(&doc, opts).unwrap()
+        };
+        let default = markdown("Account", &PrintOptions::default());
+        assert!(default.contains("    balance: u64,\n"), "{default}");
+        assert!(default.contains("    fn covers("), "{default}");
+
+        let public = markdown("Account", &opts);
+        let fields = "    pub overdraft: bool,\n    /* private fields */\n}";
+        assert!(public.contains(fields), "{public}");
+        assert!(!public.contains("fn covers("), "{public}");
+        // Trait impl items have default visibility, so are kept
+        assert!(public.contains("impl Clone for Account"), "{public}");
+
+        let private_helper = fixture_item(&doc, "private_helper");
+        assert!(!doc.is_rendered(&private_helper.id, &opts));
+        assert!(doc.is_rendered(&private_helper.id, &Default::default()));
+        // Variants have default visibility too
+        let either = markdown("Either", &opts);
+        assert!(either.contains("    Left(L),\n"), "{either}");
+    }
+
+    #[test]
+    fn test_render() {
+        let doc = fixtures_doc();
+        let mut written = Vec::new();
+        doc.write_markdown(&mut written, &PrintOptions::default())
+            .unwrap();
+        assert_eq!(doc.render(), String::from_utf8(written).unwrap());
+
+        let opts = PrintOptions {
+            format: OutputFormat::Json,
+            ..PrintOptions::default()
+        };
+        let json = doc.render_with(&opts).unwrap();
+        let json = serde_json::from_str::<Value>(&json).unwrap();
+        assert_eq!(json, doc.to_tree_json());
+    }
+
+    #[test]
+    fn test_fn_generic_params() {
+        let doc = fixtures_doc();
+        let signature = |name| fixture_item(&doc, name).signature().unwrap();
+        assert_eq!(
+            signature("parse_as"),
+            "fn parse_as<T: std::str::FromStr>(s: &str) -> Option<T>"
+        );
+        assert_eq!(
+            signature("pick"),
+            "fn pick<'a, T: Clone, const N: usize>(items: &'a [T; N], i: usize) \
+             -> &'a T"
+        );
+    }
+
+    #[test]
+    fn test_outlives_where_predicates() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions::default();
+        let definition = fixture_item(&doc, "outliving")
+            .format_definition("outliving", &doc, &opts)
+            .unwrap();
+        // Predicates are in rustdoc's order, which puts region ones last
+        assert_eq!(
+            definition,
+            "\
+pub fn outliving<'a, 'b, T>(a: &'a T, _b: &'b T) -> &'b T
+where
+    T: Send + 'a,
+    'a: 'b;
+"
+        );
+        let pair = fixture_item(&doc, "Pair").to_markdown(&doc, &opts).unwrap();
+        let impl_ = "\
+impl<'a, T> Pair<'a, T> where T: PartialOrd + 'a {
+    pub fn larger(&self) -> &'a T;
+}
+";
+        assert!(pair.contains(impl_), "{pair}");
+    }
+
+    #[test]
+    fn test_typescript() {
+        let doc = fixtures_doc();
+        let ts = doc.to_typescript(&PrintOptions::default());
+        let expected = [
+            "/**\n * A bank account.\n */\nexport interface Account { \
+                holder: string; overdraft: boolean; balance: number; }\n",
+            "export type Pairs<T> = [T, T][];\n",
+            "export type Shape = \"Circle\" | \"Square\";\n",
+            "export declare function add(a: number, b: number): number;\n",
+        ];
+        for declaration in expected {
+            assert!(ts.contains(declaration), "{declaration}\n{ts}");
+        }
+
+        let opts = PrintOptions {
+            public_only: true,
+            ..PrintOptions::default()
+        };
+        let public_ts = doc.to_typescript(&opts);
+        assert!(
+            public_ts.contains(
+                "export interface Account { holder: string; overdraft: \
+                    boolean; }\n"
+            ),
+            "{public_ts}"
+        );
+        assert_eq!(
+            format_jsdoc("Ends */ early"),
+            "/**\n * Ends *\\/ early\n */\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_output() {
+        let doc = fixtures_doc();
+        let opts = PrintOptions {
+            format: OutputFormat::Plain,
+            ..PrintOptions::default()
+        };
+        let plain = fixture_item(&doc, "add").to_markdown(&doc, &opts).unwrap();
+        // Items start with the separator, a blank line
+        assert!(
+            plain.starts_with(
+                "\nadd:\n\n    pub fn add(a: u32, b: u32) -> u32;\n\nAdds"
+            ),
+            "{plain:?}"
+        );
+        assert!(!plain.contains('`'), "{plain:?}");
     }
 }
@@ -0,0 +1,202 @@
+use std::{fs, path::Path, process::ExitCode};
+
+use ai_rustdoc::{OutputFormat, PrintOptions, RustDoc, RustDocItemKind};
+
+const USAGE: &str = "\
+Usage:
+  ai-rustdoc print <rustdoc.json> [OPTIONS]      Print docs for the whole crate
+  ai-rustdoc item <rustdoc.json> <path>          Print docs for the item at
+                                                 <path>, e.g. `hex::encode`
+  ai-rustdoc context <rustdoc.json> <path>       Print docs for the item at
+                                                 <path> and every type it
+                                                 depends on
+  ai-rustdoc kinds <rustdoc.json>                Count the items of each kind
+                                                 which aren't supported yet
+  ai-rustdoc stats <rustdoc.json>                Count the items and
+                                                 undocumented items in each
+                                                 module, largest first
+  ai-rustdoc implementors <rustdoc.json> <trait> List this crate's impls of
+                                                 <trait>, e.g. `hex::FromHex`
+  ai-rustdoc changes <old.json> <new.json>       Print the public items whose
+                                                 signatures changed between
+                                                 two versions, before and
+                                                 after
+
+Options:
+  --changed-since <ref>  Only print items whose definitions changed since the
+                         git ref <ref>. Run this in the directory `rustdoc`
+                         was run in.
+  --format <format>      One of markdown (the default), json, plain,
+                         asciidoc, html or ts.
+  --public-only          Omit items which aren't public.";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> Result<(), String> {
+    let (positionals, flags) = split_flags(args)?;
+    let mut opts = PrintOptions::default();
+    if let Some(format) = flags.format {
+        opts.format = format;
+    }
+    opts.public_only = flags.public_only;
+    match positionals.as_slice() {
+        ["print", json_path] => {
+            let doc = parse(json_path)?;
+            if let Some(since) = flags.changed_since {
+                let item_ids =
+                    doc.changed_since(Path::new("."), since).map_err(|e| {
+                        format!("Couldn't diff against {since}: {e}")
+                    })?;
+                opts.item_ids = Some(item_ids);
+            }
+            doc.print_with(&opts);
+            Ok(())
+        }
+        ["item", json_path, item_path] => {
+            let doc = parse(json_path)?;
+            let markdown = doc
+                .item_markdown(item_path, &opts)
+                .map_err(|e| e.to_string())?;
+            print!("{markdown}");
+            Ok(())
+        }
+        ["context", json_path, item_path] => {
+            let doc = parse(json_path)?;
+            let item_ids = doc.reachable_from(item_path);
+            if item_ids.is_empty() {
+                return Err(format!("No item at `{item_path}`"));
+            }
+            opts.item_ids = Some(item_ids.into_iter().collect());
+            doc.print_with(&opts);
+            Ok(())
+        }
+        ["kinds", json_path] => {
+            let doc = parse(json_path)?;
+            let mut kinds =
+                doc.unsupported_kinds().into_iter().collect::<Vec<_>>();
+            // Most common first
+            kinds.sort_by(|(a_kind, a_count), (b_kind, b_count)| {
+                b_count.cmp(a_count).then(a_kind.cmp(b_kind))
+            });
+            for (kind, count) in kinds {
+                println!("{count}\t{kind}");
+            }
+            Ok(())
+        }
+        ["stats", json_path] => {
+            let doc = parse(json_path)?;
+            let mut stats = doc.stats().into_iter().collect::<Vec<_>>();
+            // Largest first
+            stats.sort_by(|(a_path, a_stats), (b_path, b_stats)| {
+                b_stats
+                    .total()
+                    .cmp(&a_stats.total())
+                    .then(a_path.cmp(b_path))
+            });
+            println!("items\tundocumented\tmodule\tkinds");
+            for (path, module_stats) in stats {
+                let total = module_stats.total();
+                let undocumented = module_stats.total_undocumented();
+                let mut kinds = module_stats
+                    .kinds
+                    .iter()
+                    .map(|(kind, count)| (kind.to_string(), count))
+                    .collect::<Vec<_>>();
+                // Most common first
+                kinds.sort_by(|(a_kind, a_count), (b_kind, b_count)| {
+                    b_count.cmp(a_count).then(a_kind.cmp(b_kind))
+                });
+                let kinds = kinds
+                    .iter()
+                    .map(|(kind, count)| format!("{count} {kind}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("{total}\t{undocumented}\t{path}\t{kinds}");
+            }
+            Ok(())
+        }
+        ["changes", old_json_path, new_json_path] => {
+            let old = parse(old_json_path)?;
+            let new = parse(new_json_path)?;
+            let changes = new.format_changed_signatures(&old, &opts);
+            if changes.is_empty() {
+                println!("No signatures changed");
+            } else {
+                print!("{changes}");
+            }
+            Ok(())
+        }
+        ["implementors", json_path, trait_path] => {
+            let doc = parse(json_path)?;
+            let traits = doc
+                .find_by_path(trait_path)
+                .into_iter()
+                .filter(|item| item.kind() == Some(RustDocItemKind::Trait))
+                .collect::<Vec<_>>();
+            let trait_ = match traits.as_slice() {
+                [trait_] => *trait_,
+                [] => return Err(format!("No trait at `{trait_path}`")),
+                _ => return Err(format!("`{trait_path}` is ambiguous")),
+            };
+            for implementor in doc.implementors(trait_.id()) {
+                if let Some(signature) = implementor.signature() {
+                    println!("{signature}");
+                }
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+#[derive(Default)]
+struct Flags<'a> {
+    changed_since: Option<&'a str>,
+    format: Option<OutputFormat>,
+    public_only: bool,
+}
+
+/// Separates `--flag value` pairs from positional args.
+fn split_flags<'a>(
+    args: &[&'a str],
+) -> Result<(Vec<&'a str>, Flags<'a>), String> {
+    let mut positionals = Vec::new();
+    let mut flags = Flags::default();
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        match arg {
+            "--changed-since" => {
+                let since =
+                    args.next().ok_or("--changed-since requires a git ref")?;
+                flags.changed_since = Some(since);
+            }
+            "--format" => {
+                let format = args.next().ok_or("--format requires a format")?;
+                flags.format = Some(format.parse()?);
+            }
+            "--public-only" => flags.public_only = true,
+            _ if arg.starts_with("--") =>
+                return Err(format!("Unknown option {arg}\n\n{USAGE}")),
+            _ => positionals.push(arg),
+        }
+    }
+    Ok((positionals, flags))
+}
+
+/// Reads and parses the rustdoc JSON at `json_path`.
+fn parse(json_path: &str) -> Result<RustDoc, String> {
+    let json = fs::read(json_path)
+        .map_err(|e| format!("Couldn't read {json_path}: {e}"))?;
+    RustDoc::from_slice(&json)
+        .map_err(|e| format!("Couldn't parse {json_path}: {e}"))
+}
@@ -0,0 +1,915 @@
+//! Hand-written items exercising the rendering edge cases `ai-rustdoc` needs
+//! to handle. Regenerate `rustdoc.json` with `just gen-fixtures`.
+
+// For documenting a primitive, as `std` does, and a trait alias
+#![feature(rustc_attrs, rustdoc_internals, trait_alias)]
+#![allow(internal_features)]
+
+// --- Parenthesized generic args --- //
+
+/// Returns a boxed closure.
+pub fn boxed_closure() -> Box<dyn Fn(u32) -> bool> {
+    Box::new(|x| x > 0)
+}
+
+/// Returns an optional boxed closure, nesting an `Fn`-trait two generics deep.
+pub fn nested_closure() -> Option<Box<dyn Fn(&str) -> Result<u8, ()>>> {
+    None
+}
+
+/// A closure which returns nothing.
+pub fn unit_closure() -> Box<dyn FnMut(u8)> {
+    Box::new(|_| ())
+}
+
+/// A trait with a generic associated type.
+pub trait Lend {
+    type Item<F>;
+}
+
+/// Returns a boxed closure through a qualified path's own generic args.
+pub fn lend_closure<T: Lend>() -> T::Item<Box<dyn Fn(u8) -> bool>> {
+    todo!()
+}
+
+// --- Associated items in impls --- //
+
+/// Something with a numeric id.
+pub trait HasId {
+    /// The id of this type.
+    const ID: u32;
+    /// How the id is represented for display.
+    type Repr;
+}
+
+/// A simple shape.
+#[derive(Clone)]
+pub enum Shape {
+    /// A circle.
+    Circle,
+    /// A square.
+    Square,
+}
+
+impl HasId for Shape {
+    const ID: u32 = 7;
+    type Repr = String;
+}
+
+// --- API summary --- //
+
+/// Adds two numbers.
+///
+/// This paragraph is not part of the summary.
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Subtracts two numbers.
+#[deprecated(note = "use `add` with a negative number instead")]
+pub fn sub(a: i32, b: i32) -> i32 {
+    a - b
+}
+
+// --- impl Trait bounds --- //
+
+/// Iterates over borrowed numbers.
+pub fn iter_numbers<'a>(values: &'a [u32]) -> impl Iterator<Item = u32> + 'a {
+    values.iter().copied()
+}
+
+/// Sums everything in `values`.
+pub fn sum_all(values: impl IntoIterator<Item = u32> + Clone) -> u32 {
+    values.into_iter().sum()
+}
+
+// --- Re-exports --- //
+
+pub use self::inner::mul as multiply;
+
+pub mod inner {
+    /// Multiplies two numbers.
+    pub fn mul(a: u32, b: u32) -> u32 {
+        a * b
+    }
+}
+
+// --- FFI --- //
+
+extern "C" {
+    /// Computes the absolute value of an integer.
+    pub fn abs(input: i32) -> i32;
+    /// Prints a formatted string.
+    pub fn printf(format: *const u8, ...) -> i32;
+    /// The last error number.
+    pub static errno: i32;
+}
+
+/// A Rust function callable from C.
+pub extern "C" fn callback(value: u32) -> u32 {
+    value
+}
+
+// --- Higher-ranked trait bounds --- //
+
+/// Returns a predicate over borrowed strings.
+pub fn str_predicate() -> impl for<'a> Fn(&'a str) -> bool {
+    |s| s.is_empty()
+}
+
+/// Returns a boxed predicate over borrowed strings.
+pub fn boxed_str_predicate() -> Box<dyn for<'a> Fn(&'a str) -> bool> {
+    Box::new(|s| s.is_empty())
+}
+
+// --- Where clauses --- //
+
+/// Returns the size of a possibly unsized value.
+pub fn size_of_unsized<T>(value: &T) -> usize
+where
+    T: ?Sized,
+{
+    std::mem::size_of_val(value)
+}
+
+// --- Visibility --- //
+
+/// A private helper, documented since fixtures include private items.
+#[allow(dead_code)]
+fn private_helper() {}
+
+// --- Generic methods in generic impls --- //
+
+/// A wrapper around a single value.
+pub struct Wrapper<T> {
+    /// The wrapped value.
+    pub value: T,
+}
+
+impl<T> Wrapper<T> {
+    /// Wraps `value`.
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Applies `f` to the wrapped value.
+    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Wrapper<U> {
+        Wrapper {
+            value: f(self.value),
+        }
+    }
+}
+
+// --- Intra-doc links --- //
+
+/// Doubles `x`, like [`add`]ing it to itself or [multiplying by two](inner::mul).
+/// Unlike [`undocumented`], it has docs.
+///
+/// ```
+/// let v = [1, 2];
+/// assert_eq!(v[0], 1);
+/// ```
+pub fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn undocumented() {}
+
+// --- Primitives --- //
+
+/// The 32-bit signed integer type.
+#[rustc_doc_primitive = "i32"]
+mod prim_i32 {}
+
+impl HasId for i32 {
+    const ID: u32 = 32;
+    type Repr = i32;
+}
+
+// --- Fn traits in dyn and impl position --- //
+
+/// Returns a boxed closure which produces strings.
+pub fn string_source() -> Box<dyn FnMut() -> String> {
+    Box::new(String::new)
+}
+
+/// Calls `callback` with a greeting.
+pub fn with_greeting(callback: impl FnOnce(&str)) {
+    callback("hello")
+}
+
+// --- Qualified paths --- //
+
+/// Returns `value` through a reference self type.
+pub fn through_ref<T>(value: &T) -> &<&T as std::ops::Deref>::Target {
+    value
+}
+
+/// Returns an iterator through a concrete self type.
+pub fn bytes_into_iter(bytes: Vec<u8>) -> <Vec<u8> as IntoIterator>::IntoIter {
+    bytes.into_iter()
+}
+
+/// Returns an iterator through a generic self type.
+pub fn into_iter_of<T: IntoIterator>(value: T) -> <T as IntoIterator>::IntoIter {
+    value.into_iter()
+}
+
+// --- Array lengths --- //
+
+/// The size of a buffer, in bytes.
+pub const SIZE: usize = 16;
+
+/// Returns a buffer sized by a named const.
+pub fn sized_buffer() -> [u8; SIZE] {
+    [0; SIZE]
+}
+
+/// Returns a buffer sized by a computed const expression.
+pub fn doubled_buffer() -> [u8; SIZE * 2] {
+    [0; SIZE * 2]
+}
+
+/// Returns a buffer sized by a const generic in a block.
+#[allow(unused_braces)]
+pub fn block_buffer<const N: usize>() -> [u8; { N }] {
+    [0; N]
+}
+
+/// Returns a buffer sized by a const generic.
+pub fn generic_buffer<const N: usize>() -> [u8; N] {
+    [0; N]
+}
+
+/// A type with a const generic length.
+pub struct Holder<const N: usize>;
+
+/// Returns a holder whose length rustdoc can't print, so it emits `{ _ }`.
+pub fn computed_holder() -> Holder<{ SIZE + 1 }> {
+    Holder
+}
+
+// --- Multi-bound trait objects --- //
+
+use std::{error::Error, fmt::Display};
+
+/// Describes a sendable error.
+pub fn report_error(error: &(dyn Error + Send)) -> String {
+    error.to_string()
+}
+
+/// Describes any error.
+pub fn report_any_error(error: &dyn Error) -> String {
+    error.to_string()
+}
+
+/// Returns a boxed error, which doesn't need parentheses.
+pub fn boxed_error() -> Box<dyn Error + Send + Sync + 'static> {
+    "error".into()
+}
+
+/// Returns `value` as a trait object bounded by a lifetime.
+pub fn displayable<'a>(value: &'a u32) -> &'a (dyn Display + 'a) {
+    value
+}
+
+// --- Enums referenced by signatures --- //
+
+/// Names the given shape, if any.
+pub fn describe_shape(shape: Option<&Shape>) -> &'static str {
+    match shape {
+        Some(Shape::Circle) => "circle",
+        Some(Shape::Square) => "square",
+        None => "nothing",
+    }
+}
+
+// --- Where clauses on type aliases and associated types --- //
+
+/// Pairs of cloneable values.
+#[allow(type_alias_bounds)]
+pub type Pairs<T>
+where
+    T: Clone,
+= Vec<(T, T)>;
+
+/// A collection with a generic item type.
+pub trait Container {
+    /// The collection of `T`s.
+    type Item<T>: Clone
+    where
+        T: Clone;
+
+    /// Returns a collection holding the first element, if any.
+    fn first<T: Clone>(&self) -> Option<Self::Item<T>>;
+}
+
+impl<const N: usize> Container for Holder<N> {
+    type Item<T> = Vec<T> where T: Clone;
+
+    fn first<T: Clone>(&self) -> Option<Self::Item<T>> {
+        None
+    }
+}
+
+// --- Generic array and slice elements --- //
+
+/// Returns the first half of `arr`.
+pub fn first_half<T, const N: usize>(arr: &[T; N]) -> &[T] {
+    &arr[..N / 2]
+}
+
+/// Splits `items` into arrays of `N`, dropping any remainder.
+pub fn chunks_of<T: Copy, const N: usize>(items: &[T]) -> Vec<[T; N]> {
+    items
+        .chunks_exact(N)
+        .map(|chunk| std::array::from_fn(|i| chunk[i]))
+        .collect()
+}
+
+// --- Variant attributes --- //
+
+/// How much to log.
+#[derive(Default)]
+pub enum Level {
+    /// Everything.
+    Debug,
+    /// The default level.
+    #[default]
+    Info,
+    /// Problems only.
+    #[non_exhaustive]
+    #[allow(dead_code)]
+    Error,
+}
+
+// --- Tuple structs --- //
+
+/// A distance with a private unit label.
+pub struct Meters(pub u32, #[allow(dead_code)] String);
+
+/// A marker with no fields.
+pub struct Marker;
+
+/// Borrows a `T` with every kind of generic param.
+pub struct Ref<'a, 'b: 'a, T: 'a + ?Sized, const N: usize, U = u8> {
+    pub value: &'a T,
+    pub other: &'b [U; N],
+}
+
+/// Fetches bytes from somewhere.
+#[allow(async_fn_in_trait)]
+pub trait Fetch {
+    /// Fetches all the bytes.
+    async fn fetch(&self) -> Vec<u8>;
+}
+
+/// A fixed-size buffer.
+pub struct Buf<T = u8, const N: usize = 1024> {
+    pub data: [T; N],
+}
+
+/// An iterator of `T`s which can be sent between threads.
+pub trait Task<T> = Iterator<Item = T> + Send;
+
+/// Settings which may gain fields in future versions.
+#[non_exhaustive]
+pub struct Config {
+    pub verbose: bool,
+}
+
+// --- Higher-ranked where predicates --- //
+
+/// The lines which `keep` accepts.
+pub fn filter_lines<T>(lines: &[String], keep: T) -> Vec<&str>
+where
+    for<'a> T: Fn(&'a str) -> bool,
+{
+    lines.iter().map(String::as_str).filter(|line| keep(line)).collect()
+}
+
+/// The number of lines which `matches` accepts.
+pub fn count_matches<T>(lines: &[String], matches: T) -> usize
+where
+    T: for<'a> Fn(&'a str) -> bool,
+{
+    lines.iter().filter(|line| matches(line)).count()
+}
+
+// --- Enum reprs --- //
+
+/// An instruction, encoded as a `u16`.
+#[repr(u16)]
+pub enum Opcode {
+    /// Does nothing.
+    Nop = 0,
+    /// Stops the machine.
+    Halt = 0x10,
+    /// Follows `Halt`.
+    Resume,
+}
+
+// --- Receivers --- //
+
+use std::pin::Pin;
+
+/// A unit of work, with every kind of receiver.
+pub trait Job {
+    /// The job's name.
+    fn name(&self) -> &str;
+    /// Runs the job to completion.
+    fn run(&mut self);
+    /// Makes progress without blocking, returning whether the job is done.
+    fn poll(self: Pin<&mut Self>) -> bool;
+    /// Consumes the boxed job, returning its exit code.
+    fn consume(self: Box<Self>) -> u32;
+    /// Consumes the job by value.
+    fn finish(self)
+    where
+        Self: Sized;
+}
+
+// --- Tuple returns --- //
+
+/// Counts the ASCII digits at the start of `input`.
+///
+/// # Returns
+///
+/// - The number of digits at the start of `input`.
+/// - Whether they're all of `input`.
+pub fn parse_prefix(input: &str) -> (usize, bool) {
+    let digits = input.bytes().take_while(u8::is_ascii_digit).count();
+    (digits, digits == input.len())
+}
+
+// --- Nested types --- //
+
+/// A 2x2x2 cube of bytes.
+pub fn cube() -> [[[u8; 2]; 2]; 2] {
+    [[[0; 2]; 2]; 2]
+}
+
+/// Bytes, nested three deep.
+pub fn nested_vecs() -> Vec<Vec<Vec<u8>>> {
+    Vec::new()
+}
+
+// --- Trait objects with bindings --- //
+
+/// A stream of bytes which can be sent between threads.
+pub fn byte_stream() -> Box<dyn Iterator<Item = u8> + Send> {
+    Box::new(std::iter::empty())
+}
+
+/// Sums a borrowed stream of bytes.
+pub fn sum_stream(stream: &mut (dyn Iterator<Item = u8> + Send)) -> u32 {
+    stream.map(u32::from).sum()
+}
+
+// --- Pattern params --- //
+
+/// The Manhattan distance of a point from the origin, capped at `max`.
+pub fn distance((x, y): (i32, i32), Meters(max): Meters) -> u32 {
+    (x.unsigned_abs() + y.unsigned_abs()).min(max)
+}
+
+/// The only element of a 1-tuple.
+pub fn first_of((first,): (u8,)) -> u8 {
+    first
+}
+
+// --- Signatures --- //
+
+/// One of two values.
+pub enum Either<L, R>
+where
+    L: Clone,
+{
+    Left(L),
+    Right(R),
+}
+
+/// How many times `double` has been called.
+pub static mut CALLS: u32 = 0;
+
+/// Doubles an expression, like `double`.
+#[macro_export]
+macro_rules! twice {
+    ($e:expr) => {
+        $e * 2
+    };
+}
+
+// --- Bounded associated types --- //
+
+/// Something which runs asynchronously.
+pub trait Spawn {
+    /// The future which completes when the spawned work is done.
+    type Fut: std::future::Future<Output = ()> + Send;
+
+    /// Starts the work.
+    fn spawn(&self) -> Self::Fut;
+}
+
+// --- Prelude --- //
+
+/// The most commonly used items.
+pub mod prelude {
+    pub use crate::inner::*;
+    pub use crate::{add, Shape as Figure};
+}
+
+// --- Fallibility notes --- //
+
+/// Parses a log level, e.g. `warn`.
+///
+/// # Errors
+///
+/// If `s` isn't the name of a level,
+/// ignoring case.
+pub fn parse_level(s: &str) -> Result<Level, String> {
+    Err(s.to_owned())
+}
+
+/// Finds the first occurrence of `needle`.
+///
+/// # Returns
+///
+/// The index of `needle`, or `None` if it isn't in `haystack`.
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    haystack.iter().position(|byte| *byte == needle)
+}
+
+/// The last byte of `bytes`, if any.
+pub fn last_byte(bytes: &[u8]) -> Option<&u8> {
+    bytes.last()
+}
+
+// --- Sealed traits --- //
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A color space. Only this crate's color spaces are supported.
+pub trait ColorSpace: private::Sealed {
+    /// How many channels each color has.
+    fn channels(&self) -> usize;
+}
+
+// --- Associated fns with the same name --- //
+
+/// A temperature in degrees Celsius.
+pub struct Celsius(pub f64);
+
+impl Celsius {
+    /// Creates a temperature from degrees Celsius.
+    pub fn new(degrees: f64) -> Self {
+        Self(degrees)
+    }
+}
+
+// --- Auto traits --- //
+
+/// A handle to a foreign object, which mustn't leave its thread.
+pub struct Handle(*const u8);
+
+// --- Unnamed params --- //
+
+/// Receives the words of a text.
+pub trait Visitor {
+    /// Called with each word and its index.
+    fn visit(&mut self, _: &str, _: usize);
+}
+
+// --- Composite generic args --- //
+
+/// Keeps the items which pass every filter.
+pub fn filter_all(items: Vec<u32>, filters: Vec<fn(u32) -> bool>) -> Vec<u32> {
+    items
+        .into_iter()
+        .filter(|item| filters.iter().all(|filter| filter(*item)))
+        .collect()
+}
+
+/// The names of the grid cells, keyed by their coordinates.
+pub fn cell_names(
+    names: std::collections::HashMap<(u8, u8), String>,
+) -> Vec<((u8, u8), String)> {
+    names.into_iter().collect()
+}
+
+// --- Primitives --- //
+
+/// Takes every primitive type.
+#[allow(clippy::too_many_arguments, unused_variables)]
+pub fn primitives(
+    a: bool,
+    b: char,
+    c: &str,
+    d: i8,
+    e: i16,
+    f: i32,
+    g: i64,
+    h: i128,
+    i: isize,
+    j: u8,
+    k: u16,
+    l: u32,
+    m: u64,
+    n: u128,
+    o: usize,
+    p: f32,
+    q: f64,
+    r: (),
+    s: [u8; 2],
+    t: &[u16],
+    u: *const u32,
+    v: fn(),
+) -> ! {
+    panic!()
+}
+
+/// Returns the unit type explicitly.
+#[allow(clippy::unused_unit)]
+pub fn unit() -> () {}
+
+/// Returns a `str` behind a box.
+pub fn boxed_str() -> Box<str> {
+    "".into()
+}
+
+/// Returns a `char` and a `bool` in a generic.
+pub fn char_flag() -> Option<(char, bool)> {
+    None
+}
+
+// --- Doc headings --- //
+
+/// Clamps `value` to at most `max`.
+///
+/// # Examples
+///
+/// ```
+/// # use fixtures::clamp_to;
+/// assert_eq!(clamp_to(7, 5), 5);
+/// ```
+///
+/// ## Details
+///
+/// Equivalent to `value.min(max)`.
+pub fn clamp_to(value: u32, max: u32) -> u32 {
+    value.min(max)
+}
+
+// --- Explicit futures --- //
+
+/// A downloaded body.
+pub struct Bytes(pub Vec<u8>);
+
+/// Downloads `url`, without `async fn` sugar.
+pub fn download(
+    url: String,
+) -> impl std::future::Future<Output = Result<Bytes, std::io::Error>> + Send {
+    async move { Ok(Bytes(url.into_bytes())) }
+}
+
+// --- Reachability --- //
+
+/// Plans a route through `stops`.
+pub fn plan_route(stops: &[Stop]) -> Route {
+    Route {
+        stops: stops.to_vec(),
+        alternative: None,
+    }
+}
+
+/// A planned route.
+pub struct Route {
+    pub stops: Vec<Stop>,
+    /// Another way there, if any.
+    pub alternative: Option<Box<Route>>,
+}
+
+/// A stop on a [`Route`].
+#[derive(Clone)]
+pub struct Stop {
+    pub name: String,
+    pub kind: StopKind,
+}
+
+/// How a [`Stop`] is served.
+#[derive(Clone)]
+pub enum StopKind {
+    Bus,
+    Rail(Platform),
+}
+
+/// A numbered platform.
+#[derive(Clone)]
+pub struct Platform(pub u8);
+
+// --- Mixed variant shapes --- //
+
+/// A frame on the wire, tagged by its discriminant.
+#[repr(u8)]
+pub enum Frame {
+    Ping = 1,
+    Data(u8),
+    Close { code: u32 },
+}
+
+// --- Generic and reference impl targets --- //
+
+/// A value with a label.
+pub struct Labeled<T>(pub T);
+
+impl<T: std::fmt::Display> std::fmt::Display for Labeled<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "label: {}", self.0)
+    }
+}
+
+/// A gauge, displayed by reference.
+pub struct Gauge;
+
+impl std::fmt::Display for &Gauge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("gauge")
+    }
+}
+
+// --- Supertrait chains --- //
+
+/// Something with a name.
+pub trait Named {
+    /// The name.
+    fn name(&self) -> String;
+}
+
+/// Greets by name.
+pub trait Greeter: Named {
+    fn greet(&self) -> String;
+}
+
+/// Greets loudly.
+pub trait LoudGreeter: Greeter + Send {
+    fn shout(&self) -> String;
+}
+
+// --- Large values --- //
+
+/// The squares of 0 to 31.
+pub static SQUARES: [u16; 32] = [
+    0, 1, 4, 9, 16, 25, 36, 49, 64, 81, 100, 121, 144, 169, 196, 225, 256, 289,
+    324, 361, 400, 441, 484, 529, 576, 625, 676, 729, 784, 841, 900, 961,
+];
+
+/// The license text shown by `--version`.
+pub const LICENSE_NOTICE: &str = "Permission is hereby granted, free of charge, to any person obtaining a copy of this software and associated documentation files";
+
+// --- Multi-line where clauses --- //
+
+/// Merges `items` into `into`, keyed by `key`.
+pub fn merge_by<K, V, F>(into: &mut Vec<V>, items: Vec<V>, key: F)
+where
+    K: Ord,
+    V: Clone + Send,
+    F: Fn(&V) -> K,
+{
+    let _ = (key, items);
+    into.clear();
+}
+
+/// A cache of values keyed by `K`.
+pub struct Cache<K, V>
+where
+    K: std::hash::Hash + Eq,
+    V: Clone,
+{
+    pub entries: Vec<(K, V)>,
+}
+
+// --- Fully qualified methods --- //
+
+/// A running total.
+pub struct Tally<T>(pub T);
+
+impl Tally<u32> {
+    /// Adds `x` to the total, returning the new total.
+    pub fn push(&mut self, x: u32) -> u32 {
+        self.0 += x;
+        self.0
+    }
+
+    /// Starts counting from zero.
+    pub fn zero() -> Self {
+        Self(0)
+    }
+}
+
+// --- Const generic trait args --- //
+
+/// Splits data into chunks of `N` bytes.
+pub trait Chunked<const N: usize> {
+    /// The first chunk.
+    fn first_chunk(&self) -> [u8; N];
+}
+
+/// A four byte block.
+pub struct Block(pub [u8; 4]);
+
+impl Chunked<4> for Block {
+    fn first_chunk(&self) -> [u8; 4] {
+        self.0
+    }
+}
+
+/// Reads a chunk from a bound in the generic params.
+pub fn read_chunk<T: Chunked<4>>(data: &T) -> [u8; 4] {
+    data.first_chunk()
+}
+
+/// Reads a chunk from a bound in the where clause.
+pub fn read_wide_chunk<T>(data: &T) -> [u8; 8]
+where
+    T: Chunked<8>,
+{
+    data.first_chunk()
+}
+
+/// A chunker behind an impl trait.
+pub fn chunker() -> impl Chunked<4> {
+    Block([0; 4])
+}
+
+/// A chunker behind a trait object.
+pub fn boxed_chunker() -> Box<dyn Chunked<4>> {
+    Box::new(Block([0; 4]))
+}
+
+// --- Struct field docs --- //
+
+/// A bank account.
+#[derive(Clone)]
+pub struct Account {
+    /// Who holds the account.
+    ///
+    /// Joint accounts list the primary holder.
+    pub holder: String,
+    pub overdraft: bool,
+    #[allow(dead_code)]
+    balance: u64,
+}
+
+impl Account {
+    /// Borrows the holder's name.
+    pub fn holder_name<'a>(&'a self) -> &'a str {
+        &self.holder
+    }
+
+    /// Whether the balance covers `amount`.
+    #[allow(dead_code)]
+    fn covers(&self, amount: u64) -> bool {
+        self.balance >= amount
+    }
+}
+
+/// A duration in milliseconds.
+pub struct Millis(pub u64);
+
+// --- Generic fns --- //
+
+/// Parses `s` as any `T`.
+pub fn parse_as<T: std::str::FromStr>(s: &str) -> Option<T> {
+    s.parse().ok()
+}
+
+/// Picks the `i`th of `N` items, with every kind of generic param.
+pub fn pick<'a, T: Clone, const N: usize>(items: &'a [T; N], i: usize) -> &'a T {
+    &items[i % N]
+}
+
+// --- Where clauses with outlives predicates --- //
+
+/// Returns `a`, which outlives `b`.
+pub fn outliving<'a, 'b, T>(a: &'a T, _b: &'b T) -> &'b T
+where
+    'a: 'b,
+    T: Send + 'a,
+{
+    a
+}
+
+/// Two borrowed values.
+pub struct Pair<'a, T>(pub &'a T, pub &'a T);
+
+impl<'a, T> Pair<'a, T>
+where
+    T: PartialOrd + 'a,
+{
+    /// The larger of the two.
+    pub fn larger(&self) -> &'a T {
+        if self.0 >= self.1 { self.0 } else { self.1 }
+    }
+}
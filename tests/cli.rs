@@ -0,0 +1,135 @@
+//! Runs the `ai-rustdoc` binary against the fixtures.
+
+const BIN: &str = env!("CARGO_BIN_EXE_ai-rustdoc");
+const FIXTURES_JSON: &str = "test-data/fixtures/rustdoc.json";
+
+/// Runs the binary with `args`, returning its stdout, or its stderr if it
+/// failed.
+fn run(args: &[&str]) -> Result<String, String> {
+    let output = duct::cmd(BIN, args)
+        .stdout_capture()
+        .stderr_capture()
+        .unchecked()
+        .run()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        Err(stderr)
+    }
+}
+
+#[test]
+fn test_format_flag() {
+    let default = run(&["print", FIXTURES_JSON]).unwrap();
+    let markdown = run(&["print", FIXTURES_JSON, "--format", "markdown"]);
+    assert_eq!(markdown.unwrap(), default);
+    assert!(
+        default.starts_with("# Crate fixtures v0.1.0\n"),
+        "{default}"
+    );
+
+    let json = run(&["print", FIXTURES_JSON, "--format", "json"]).unwrap();
+    let tree = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+    assert_eq!(tree["name"], "fixtures");
+
+    let asciidoc = run(&["print", FIXTURES_JSON, "--format", "asciidoc"]);
+    let asciidoc = asciidoc.unwrap();
+    assert!(
+        asciidoc.starts_with("= Crate fixtures v0.1.0\n"),
+        "{asciidoc}"
+    );
+
+    let plain = run(&["print", FIXTURES_JSON, "--format", "plain"]).unwrap();
+    assert!(plain.starts_with("Crate fixtures v0.1.0\n"), "{plain}");
+    // Definitions are indented rather than fenced
+    assert!(
+        plain.contains("\n    pub fn add(a: u32, b: u32) -> u32;\n"),
+        "{plain}"
+    );
+
+    let html = run(&["print", FIXTURES_JSON, "--format", "html"]).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"), "{html}");
+    assert!(
+        html.contains("<title>Crate fixtures v0.1.0</title>"),
+        "{html}"
+    );
+
+    let ts = run(&["print", FIXTURES_JSON, "--format", "ts"]).unwrap();
+    assert!(
+        ts.contains("export interface Buf<T> { data: T[]; }\n"),
+        "{ts}"
+    );
+
+    let item = run(&[
+        "item",
+        FIXTURES_JSON,
+        "fixtures::add",
+        "--format",
+        "asciidoc",
+    ]);
+    assert!(item.unwrap().contains("=== `add`"));
+}
+
+#[test]
+fn test_public_only() {
+    let json = run(&["print", FIXTURES_JSON, "--format", "json"]).unwrap();
+    assert!(json.contains("\"private_helper\""), "{json}");
+
+    let args = ["print", FIXTURES_JSON, "--format", "json", "--public-only"];
+    let public_json = run(&args).unwrap();
+    let tree = serde_json::from_str::<serde_json::Value>(&public_json);
+    assert_eq!(tree.unwrap()["name"], "fixtures");
+    assert!(!public_json.contains("\"private_helper\""), "{public_json}");
+}
+
+#[test]
+fn test_unknown_format() {
+    let err = run(&["print", FIXTURES_JSON, "--format", "yaml"]);
+    assert_eq!(
+        err.unwrap_err().trim_end(),
+        "Unknown format `yaml`. Valid formats: markdown, json, plain, \
+         asciidoc, html, ts"
+    );
+    let err = run(&["print", FIXTURES_JSON, "--format"]).unwrap_err();
+    assert_eq!(err.trim_end(), "--format requires a format");
+}
+
+#[test]
+fn test_context() {
+    let context = run(&["context", FIXTURES_JSON, "fixtures::Stop"]).unwrap();
+    for name in ["Stop", "StopKind", "Platform"] {
+        let heading = format!("`{name}`:\n");
+        assert!(context.contains(&heading), "{name}:\n{context}");
+    }
+    assert!(!context.contains("`Route`:\n"), "{context}");
+
+    let missing = run(&["context", FIXTURES_JSON, "fixtures::Missing"]);
+    assert_eq!(missing.unwrap_err(), "No item at `fixtures::Missing`\n");
+}
+
+#[test]
+fn test_stats() {
+    let stats = run(&["stats", FIXTURES_JSON]).unwrap();
+    let mut lines = stats.lines();
+    assert_eq!(lines.next(), Some("items\tundocumented\tmodule\tkinds"));
+    // The root module is the largest
+    let root = lines.next().unwrap();
+    assert!(root.contains("\tfixtures\t"), "{stats}");
+    assert!(
+        stats.contains("1\t0\tfixtures::inner\t1 function\n"),
+        "{stats}"
+    );
+}
+
+#[test]
+fn test_changes() {
+    // Signature changes themselves are covered by the library's tests
+    let changes = run(&["changes", FIXTURES_JSON, FIXTURES_JSON]).unwrap();
+    assert_eq!(changes, "No signatures changed\n");
+
+    let err = run(&["changes", FIXTURES_JSON]).unwrap_err();
+    assert!(err.starts_with("Usage:"), "{err}");
+}
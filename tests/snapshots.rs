@@ -0,0 +1,55 @@
+//! Snapshot tests: renders the bundled crates and compares the output against
+//! the snapshots in `tests/snapshots`, so that any change to the rendered
+//! output shows up in review.
+//!
+//! If a change to the output is intended, review and accept the new
+//! snapshots with [`cargo insta`](https://insta.rs/docs/cli/), then commit
+//! them with the code change:
+//!
+//! ```sh
+//! cargo insta test --review
+//! ```
+//!
+//! Without `cargo insta`, `INSTA_UPDATE=always cargo test --test snapshots`
+//! overwrites the snapshots, to be reviewed with `git diff tests/snapshots`.
+
+use std::{fs, path::Path};
+
+use ai_rustdoc::{PrintOptions, RustDoc, SortMode};
+
+/// Renders `test-data/{dir}/rustdoc.json` with `opts`.
+fn render(dir: &str, opts: &PrintOptions) -> String {
+    let json_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test-data")
+        .join(dir)
+        .join("rustdoc.json");
+    let json = fs::read(json_path).unwrap();
+    let doc = RustDoc::from_slice(&json).unwrap();
+    let mut rendered = Vec::new();
+    doc.write_markdown(&mut rendered, opts).unwrap();
+    String::from_utf8(rendered).unwrap()
+}
+
+#[test]
+fn test_hex_snapshot() {
+    let rendered = render("hex", &PrintOptions::default());
+    insta::assert_snapshot!("hex", rendered);
+}
+
+#[test]
+fn test_common_snapshot() {
+    let rendered = render("common", &PrintOptions::default());
+    insta::assert_snapshot!("common", rendered);
+}
+
+/// Items are grouped by module, then kind, then name. The fixtures are
+/// small, but have the `inner`, `prelude` and `private` modules to group.
+#[test]
+fn test_fixtures_structured_snapshot() {
+    let opts = PrintOptions {
+        sort: SortMode::Structured,
+        ..PrintOptions::default()
+    };
+    let rendered = render("fixtures", &opts);
+    insta::assert_snapshot!("fixtures_structured", rendered);
+}